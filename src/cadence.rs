@@ -0,0 +1,375 @@
+//! Frame rate inference and cadence analysis of timestamp series.
+//!
+//! Ingested footage and logged render timings often come as a list of
+//! ticks without a reliable rate. A [`Cadence`] fits a frame grid to such a
+//! series and reports the best-fit rate, how far the timestamps stray from
+//! the grid, and which frames are missing or repeated.
+//!
+//! # Examples
+//!
+//! ```
+//! use frame_tick::{
+//!     FrameRate, Tick,
+//!     cadence::{Cadence, Gap},
+//! };
+//!
+//! // A 25 fps capture with a millisecond of jitter, a dropped frame and a
+//! // repeated one.
+//! let millis = [0, 41, 80, 121, 200, 240, 240, 281];
+//! let timestamps = millis.map(|millis| Tick::from_secs_int(millis) / 1000);
+//!
+//! let cadence = Cadence::analyze(&timestamps, 0.1)?;
+//! assert_eq!(FrameRate::PAL, cadence.frame_rate());
+//! assert_eq!([0, 1, 2, 3, 5, 6, 6, 7], cadence.frames());
+//! let gap = Gap {
+//!     index: 4,
+//!     missing: 1,
+//! };
+//! assert_eq!([gap], cadence.gaps());
+//! assert_eq!([6], cadence.duplicates());
+//! assert!(cadence.jitter().max < Tick::from_secs_int(2) / 1000);
+//! # Ok::<(), frame_tick::cadence::CadenceError>(())
+//! ```
+use crate::{
+    FrameRate, Rounding, TICKS_PER_SECOND, Tick, best_rational, catalog,
+    div_round, quantize::Grid,
+};
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+
+/// An error analyzing a timestamp series.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum CadenceError {
+    /// There are fewer than two distinct timestamps.
+    TooShort,
+    /// The timestamp at the index is before the one before it.
+    NotSorted {
+        /// The index of the timestamp.
+        index: usize,
+    },
+    /// The fitted rate does not fit a [`FrameRate`].
+    Rate,
+    /// A frame number or the jitter does not fit.
+    Overflow,
+}
+
+impl Display for CadenceError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooShort => write!(f, "fewer than two distinct timestamps"),
+            Self::NotSorted { index } => {
+                write!(f, "timestamp {index} is before the one before it")
+            }
+            Self::Rate => write!(f, "fitted rate is not a valid frame rate"),
+            Self::Overflow => write!(f, "timestamps span too many frames"),
+        }
+    }
+}
+
+impl core::error::Error for CadenceError {}
+
+/// Missing frames before a timestamp.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Gap {
+    /// The index of the timestamp after the gap.
+    pub index: usize,
+    /// The number of frames missing before it.
+    pub missing: u64,
+}
+
+/// How far timestamps are from their frame on the grid.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Jitter {
+    /// The mean absolute deviation.
+    pub mean: Tick,
+    /// The root mean square deviation, rounded down.
+    pub rms: Tick,
+    /// The largest absolute deviation.
+    pub max: Tick,
+}
+
+/// The frame grid fitted to a timestamp series, see the
+/// [module documentation](self).
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Cadence {
+    grid: Grid,
+    frame_rate: FrameRate,
+    frames: Vec<i64>,
+    jitter: Jitter,
+    gaps: Vec<Gap>,
+    duplicates: Vec<usize>,
+    pulldown: bool,
+}
+
+impl Cadence {
+    /// Fits a frame grid to the sorted `timestamps`.
+    ///
+    /// Frames are first numbered by the median frame duration, which
+    /// ignores the odd dropped or repeated frame. The rate is then fitted to
+    /// them by least squares and snapped to the closest rate of the
+    /// [`catalog`] within `tolerance` frames per second, or otherwise to the
+    /// closest rate with a denominator of at most 1001.
+    pub fn analyze(
+        timestamps: &[Tick],
+        tolerance: f64,
+    ) -> Result<Self, CadenceError> {
+        if let Some(index) =
+            timestamps.windows(2).position(|pair| pair[1] < pair[0])
+        {
+            return Err(CadenceError::NotSorted { index: index + 1 });
+        }
+        let steps: Vec<i128> = timestamps
+            .windows(2)
+            .map(|pair| step(pair[0], pair[1]))
+            .filter(|&step| 0 < step)
+            .collect();
+        let (Some(&first), Some(&last)) =
+            (timestamps.first(), timestamps.last())
+        else {
+            return Err(CadenceError::TooShort);
+        };
+
+        let fitted = fit_rate(timestamps, two_frames(&steps)?);
+        let frame_rate = catalog::iter()
+            .map(|entry| entry.frame_rate())
+            .filter(|rate| (rate.to_f64() - fitted).abs() <= tolerance)
+            .min_by(|a, b| {
+                (a.to_f64() - fitted)
+                    .abs()
+                    .total_cmp(&(b.to_f64() - fitted).abs())
+            })
+            .or_else(|| {
+                best_rational(fitted, 1001)
+                    .and_then(|(num, den)| FrameRate::from_u64(num, den))
+            })
+            .ok_or(CadenceError::Rate)?;
+
+        // Center the grid on the timestamps.
+        let grid = Grid::frames(frame_rate).with_origin(first);
+        let offset = timestamps
+            .iter()
+            .map(|&tick| deviation(grid, tick))
+            .sum::<i128>()
+            / timestamps.len() as i128;
+        let origin =
+            (first.0 as i128 + offset).clamp(i64::MIN as _, i64::MAX as _);
+        let grid = grid.with_origin(Tick(origin as _));
+
+        // The frame numbers are close to zero at the first timestamp and
+        // must fit at the last.
+        let last_frame = div_round(
+            step(grid.origin(), last) * frame_rate.num() as i128,
+            TICKS_PER_SECOND as i128 * frame_rate.den() as i128,
+            Rounding::Nearest,
+        );
+        if i64::MAX as i128 <= last_frame {
+            return Err(CadenceError::Overflow);
+        }
+
+        let frames: Vec<i64> = timestamps
+            .iter()
+            .map(|&tick| grid.index(tick, Rounding::Nearest))
+            .collect();
+        let (mut gaps, mut duplicates) = (Vec::new(), Vec::new());
+        for (index, pair) in frames.windows(2).enumerate() {
+            match pair[1] - pair[0] {
+                0 => duplicates.push(index + 1),
+                1 => {}
+                step => gaps.push(Gap {
+                    index: index + 1,
+                    missing: step as u64 - 1,
+                }),
+            }
+        }
+
+        let (mut sum, mut squares, mut max) = (0i128, 0u128, 0);
+        for &tick in timestamps {
+            let deviation = deviation(grid, tick).unsigned_abs();
+            sum += deviation as i128;
+            squares = squares
+                .checked_add(
+                    deviation
+                        .checked_mul(deviation)
+                        .ok_or(CadenceError::Overflow)?,
+                )
+                .ok_or(CadenceError::Overflow)?;
+            max = max.max(deviation);
+        }
+        let count = timestamps.len();
+        let jitter = Jitter {
+            mean: Tick(div_round(sum, count as _, Rounding::Nearest) as _),
+            rms: Tick((squares / count as u128).isqrt() as _),
+            max: Tick(max as _),
+        };
+
+        let pulldown = is_pulldown(timestamps, &frames, frame_rate);
+        Ok(Self {
+            grid,
+            frame_rate,
+            frames,
+            jitter,
+            gaps,
+            duplicates,
+            pulldown,
+        })
+    }
+
+    /// The best-fit rate.
+    #[inline]
+    pub fn frame_rate(&self) -> FrameRate {
+        self.frame_rate
+    }
+
+    /// The fitted frame grid, centered on the timestamps.
+    #[inline]
+    pub fn grid(&self) -> Grid {
+        self.grid
+    }
+
+    /// The frame of each timestamp, counted from the
+    /// [origin](Grid::origin) of the [`grid()`](Self::grid).
+    #[inline]
+    pub fn frames(&self) -> &[i64] {
+        &self.frames
+    }
+
+    /// The deviation of the timestamps from their frames.
+    #[inline]
+    pub fn jitter(&self) -> Jitter {
+        self.jitter
+    }
+
+    /// The gaps of missing frames, in ascending order.
+    #[inline]
+    pub fn gaps(&self) -> &[Gap] {
+        &self.gaps
+    }
+
+    /// The indices of the timestamps on the same frame as the one before.
+    #[inline]
+    pub fn duplicates(&self) -> &[usize] {
+        &self.duplicates
+    }
+
+    /// Returns `true` if frames last either two or three video fields, as
+    /// with 2:3 pulldown of film to video.
+    ///
+    /// The [`frame_rate()`](Self::frame_rate) is then that of the film,
+    /// e.g. 23.976 for video at 29.97.
+    #[inline]
+    pub fn is_pulldown(&self) -> bool {
+        self.pulldown
+    }
+}
+
+/// The duration of two frames in ticks.
+///
+/// This is twice the median duration of distinct frames, which ignores
+/// the odd dropped or repeated frame. If a quarter of the durations are 3/2
+/// or 2/3 of that, as with 2:3 pulldown, it is the median duration of two
+/// consecutive frames instead.
+fn two_frames(steps: &[i128]) -> Result<i128, CadenceError> {
+    let median = |mut values: Vec<i128>| {
+        let middle = values.len() / 2;
+        *values.select_nth_unstable(middle).1
+    };
+    if steps.is_empty() {
+        return Err(CadenceError::TooShort);
+    }
+    let step = median(steps.to_vec());
+
+    // Within a tenth of `ratio` times the median.
+    let near = |other: i128, ratio: (i128, i128)| {
+        (other * ratio.1 - step * ratio.0).abs() * 10 <= step * ratio.0
+    };
+    let pulldown = steps
+        .iter()
+        .filter(|&&other| near(other, (3, 2)) || near(other, (2, 3)))
+        .count();
+    if steps.len() <= 4 * pulldown {
+        Ok(median(
+            steps.windows(2).map(|pair| pair[0] + pair[1]).collect(),
+        ))
+    } else {
+        Ok(2 * step)
+    }
+}
+
+/// The rate of the least squares fit of the timestamps to their frame
+/// numbers at `two_frames` ticks per two frames.
+///
+/// Each step is numbered on its own, so an estimate slightly off does not
+/// add up over long series.
+fn fit_rate(timestamps: &[Tick], two_frames: i128) -> f64 {
+    let first = timestamps[0];
+    let mut frame = 0;
+    let points: Vec<_> = timestamps
+        .iter()
+        .scan(first, |previous, &tick| {
+            let ticks = step(*previous, tick);
+            frame += div_round(2 * ticks, two_frames, Rounding::Nearest);
+            *previous = tick;
+            Some((frame as f64, step(first, tick) as f64))
+        })
+        .collect();
+
+    let count = points.len() as f64;
+    let (sum_n, sum_x) = points
+        .iter()
+        .fold((0.0, 0.0), |(sum_n, sum_x), (n, x)| (sum_n + n, sum_x + x));
+    let (mean_n, mean_x) = (sum_n / count, sum_x / count);
+    let (covariance, variance) =
+        points
+            .iter()
+            .fold((0.0, 0.0), |(covariance, variance), (n, x)| {
+                (
+                    covariance + (n - mean_n) * (x - mean_x),
+                    variance + (n - mean_n) * (n - mean_n),
+                )
+            });
+    // The longest step is at least a frame, so neither is zero.
+    TICKS_PER_SECOND as f64 * variance / covariance
+}
+
+/// The ticks from `from` to `to`, which may not fit a [`Tick`].
+#[inline]
+fn step(from: Tick, to: Tick) -> i128 {
+    to.0 as i128 - from.0 as i128
+}
+
+/// The offset of `tick` from the nearest point of `grid`.
+#[inline]
+fn deviation(grid: Grid, tick: Tick) -> i128 {
+    step(grid.point(grid.index(tick, Rounding::Nearest)), tick)
+}
+
+/// Whether consecutive frames last 4/5 or 6/5 of a frame at `frame_rate`,
+/// each at least twice.
+fn is_pulldown(
+    timestamps: &[Tick],
+    frames: &[i64],
+    frame_rate: FrameRate,
+) -> bool {
+    // Durations are scaled by `num`, so a frame lasts this long. Within a
+    // tenth of a frame is near.
+    let frame = TICKS_PER_SECOND as i128 * frame_rate.den() as i128;
+    let near = |step: i128, fifths: i128| {
+        (5 * step - fifths * frame).abs() * 2 <= frame
+    };
+
+    let (mut short, mut long) = (0, 0);
+    for (ticks, frames) in timestamps.windows(2).zip(frames.windows(2)) {
+        if frames[1] - frames[0] != 1 {
+            continue;
+        }
+        let step = step(ticks[0], ticks[1]) * frame_rate.num() as i128;
+        if near(step, 4) {
+            short += 1;
+        } else if near(step, 6) {
+            long += 1;
+        } else {
+            return false;
+        }
+    }
+    2 <= short && 2 <= long
+}
@@ -0,0 +1,161 @@
+//! A catalog of common broadcast, film and display frame rates.
+//!
+//! Each entry carries metadata for driving e.g. a settings UI.
+//!
+//! # Examples
+//!
+//! ```
+//! use frame_tick::{FrameRate, catalog};
+//!
+//! let ntsc = catalog::by_name("29.97").unwrap();
+//! assert_eq!(FrameRate::NTSC, ntsc.frame_rate());
+//! assert!(ntsc.is_drop_frame_eligible());
+//! assert!(ntsc.is_interlaced_capable());
+//!
+//! // All entries, in ascending order.
+//! assert!(catalog::iter().is_sorted_by_key(|entry| entry.frame_rate()));
+//! ```
+use crate::{FrameRate, timecode::supports_drop_frame};
+
+/// A frame rate with metadata.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct CatalogEntry {
+    name: &'static str,
+    description: &'static str,
+    frame_rate: FrameRate,
+    interlaced_capable: bool,
+}
+
+impl CatalogEntry {
+    const fn new(
+        name: &'static str,
+        description: &'static str,
+        frame_rate: FrameRate,
+        interlaced_capable: bool,
+    ) -> Self {
+        Self {
+            name,
+            description,
+            frame_rate,
+            interlaced_capable,
+        }
+    }
+
+    /// The short name, e.g. `"23.976"`. Use this with [`by_name()`].
+    #[inline]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// What the frame rate is used for, e.g. `"Film on NTSC video"`.
+    #[inline]
+    pub const fn description(&self) -> &'static str {
+        self.description
+    }
+
+    /// The frame rate.
+    #[inline]
+    pub const fn frame_rate(&self) -> FrameRate {
+        self.frame_rate
+    }
+
+    /// Whether timecodes at this rate can use drop-frame counting.
+    #[inline]
+    pub const fn is_drop_frame_eligible(&self) -> bool {
+        supports_drop_frame(self.frame_rate)
+    }
+
+    /// Whether this is a frame rate of common interlaced video formats,
+    /// with two fields per frame (e.g. 50i for 25 fps).
+    #[inline]
+    pub const fn is_interlaced_capable(&self) -> bool {
+        self.interlaced_capable
+    }
+
+    /// Whether every frame starts on an integral tick at the current
+    /// [`TICKS_PER_SECOND`](crate::TICKS_PER_SECOND).
+    ///
+    /// See [`FrameRate::is_exact()`].
+    #[inline]
+    pub const fn is_exact(&self) -> bool {
+        self.frame_rate.is_exact()
+    }
+}
+
+/// All catalog entries, in ascending order of frame rate.
+pub const CATALOG: &[CatalogEntry] = &[
+    CatalogEntry::new("12.5", "Half of PAL", FrameRate::FPS_12_5, false),
+    CatalogEntry::new("14.985", "Half of NTSC", FrameRate::FPS_14_985, false),
+    CatalogEntry::new("15", "Half of 30 fps", FrameRate::FPS_15, false),
+    CatalogEntry::new(
+        "23.976",
+        "Film on NTSC video",
+        FrameRate::NTSC_FILM,
+        false,
+    ),
+    CatalogEntry::new("24", "Film", FrameRate::FILM, false),
+    CatalogEntry::new("25", "PAL", FrameRate::PAL, true),
+    CatalogEntry::new("29.97", "NTSC", FrameRate::NTSC, true),
+    CatalogEntry::new("30", "30 fps", FrameRate::FPS_30, true),
+    CatalogEntry::new(
+        "47.952",
+        "High frame rate film on NTSC video",
+        FrameRate::FPS_47_952,
+        false,
+    ),
+    CatalogEntry::new("48", "High frame rate film", FrameRate::FPS_48, false),
+    CatalogEntry::new("50", "PAL high frame rate", FrameRate::PAL_HIGH, false),
+    CatalogEntry::new(
+        "59.94",
+        "NTSC high frame rate",
+        FrameRate::NTSC_HIGH,
+        false,
+    ),
+    CatalogEntry::new("60", "60 fps", FrameRate::FPS_60, false),
+    CatalogEntry::new(
+        "72",
+        "Triple-flash film projection, Oculus Quest 1",
+        FrameRate::FPS_72,
+        false,
+    ),
+    CatalogEntry::new("90", "VR headsets", FrameRate::FPS_90, false),
+    CatalogEntry::new("96", "Quadruple film rate", FrameRate::FPS_96, false),
+    CatalogEntry::new(
+        "100",
+        "Double PAL high frame rate",
+        FrameRate::FPS_100,
+        false,
+    ),
+    CatalogEntry::new("119.88", "NTSC 120 fps", FrameRate::FPS_119_88, false),
+    CatalogEntry::new(
+        "120",
+        "High refresh rate displays",
+        FrameRate::FPS_120,
+        false,
+    ),
+    CatalogEntry::new("144", "Gaming displays", FrameRate::FPS_144, false),
+    CatalogEntry::new("165", "Gaming displays", FrameRate::FPS_165, false),
+    CatalogEntry::new(
+        "240",
+        "Gaming displays, slow motion capture",
+        FrameRate::FPS_240,
+        false,
+    ),
+];
+
+/// Iterate over all catalog entries, in ascending order of frame rate.
+#[inline]
+pub fn iter() -> core::slice::Iter<'static, CatalogEntry> {
+    CATALOG.iter()
+}
+
+/// Look up a catalog entry by its [name](CatalogEntry::name), e.g.
+/// `"59.94"`.
+pub fn by_name(name: &str) -> Option<&'static CatalogEntry> {
+    CATALOG.iter().find(|entry| name == entry.name)
+}
+
+/// Look up the catalog entry of `frame_rate`, if there is one.
+pub fn by_frame_rate(frame_rate: FrameRate) -> Option<&'static CatalogEntry> {
+    CATALOG.iter().find(|entry| frame_rate == entry.frame_rate)
+}
@@ -0,0 +1,31 @@
+//! Conversions between [`Tick`] and [`chrono::TimeDelta`].
+//!
+//! Both directions round to the nearest nanosecond or tick, see
+//! [`Tick::to_nanos()`].
+use crate::{DurationError, NANOS_PER_SECOND, Rounding, Tick};
+use chrono::TimeDelta;
+
+impl From<Tick> for TimeDelta {
+    /// Every tick is in range of a `TimeDelta`.
+    fn from(tick: Tick) -> Self {
+        let nanos = tick.to_nanos(Rounding::Nearest);
+
+        TimeDelta::new(
+            nanos.div_euclid(NANOS_PER_SECOND) as _,
+            nanos.rem_euclid(NANOS_PER_SECOND) as _,
+        )
+        .expect("ticks are in range of a TimeDelta")
+    }
+}
+
+impl TryFrom<TimeDelta> for Tick {
+    type Error = DurationError;
+
+    fn try_from(delta: TimeDelta) -> Result<Self, Self::Error> {
+        Tick::from_nanos(
+            delta.num_seconds() as i128 * NANOS_PER_SECOND
+                + delta.subsec_nanos() as i128,
+            Rounding::Nearest,
+        )
+    }
+}
@@ -0,0 +1,273 @@
+//! Final Cut Pro XML rational time values.
+//!
+//! FCPXML expresses offsets and durations as rational numbers of seconds,
+//! e.g. `"3003/30000s"` or `"10s"`, and the frame rate of a sequence as its
+//! `frameDuration`, e.g. `"1001/30000s"`.
+//!
+//! [`FcpxmlTime`] parses and prints this grammar without ever going through
+//! `f64`.
+//!
+//! # Examples
+//!
+//! ```
+//! use frame_tick::{FrameRate, Tick, fcpxml::FcpxmlTime};
+//!
+//! let time: FcpxmlTime = "48/24s".parse().unwrap();
+//! assert_eq!(Ok(Tick::from_secs(2.0)), time.to_tick());
+//!
+//! let frame_rate = FrameRate::from_frame_duration("100/2400s").unwrap();
+//! assert_eq!(FrameRate::FILM, frame_rate);
+//! assert_eq!("1/24s", frame_rate.frame_duration().to_string());
+//! ```
+use crate::{
+    FrameRate, Rounding, TICKS_PER_SECOND, Tick, div_round, frame_start, gcd,
+    parse_digits,
+};
+use core::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+/// An error parsing or converting an FCPXML time value.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum FcpxmlError {
+    /// The string is not of the form `N/Ds` or `Ns`.
+    Syntax,
+    /// The denominator is zero.
+    ZeroDenominator,
+    /// The time is not an integral number of ticks. Carries the nearest
+    /// [`Tick`].
+    Inexact {
+        /// The nearest tick to the requested time.
+        nearest: Tick,
+    },
+    /// A frame duration that is zero, negative or does not fit a
+    /// [`FrameRate`].
+    InvalidFrameDuration,
+    /// The result does not fit into a [`Tick`].
+    Overflow,
+}
+
+impl Display for FcpxmlError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Syntax => {
+                write!(f, "FCPXML time must be of the form `N/Ds` or `Ns`")
+            }
+            Self::ZeroDenominator => {
+                write!(f, "FCPXML time has a zero denominator")
+            }
+            Self::Inexact { nearest } => write!(
+                f,
+                "FCPXML time is not an integral number of ticks (nearest: {})",
+                nearest.0
+            ),
+            Self::InvalidFrameDuration => {
+                write!(f, "FCPXML frame duration is not a valid frame rate")
+            }
+            Self::Overflow => write!(f, "FCPXML time overflows a tick"),
+        }
+    }
+}
+
+impl core::error::Error for FcpxmlError {}
+
+/// A rational number of seconds, as used for FCPXML `offset`, `start`,
+/// `duration` and `frameDuration` attributes.
+///
+/// The value is kept exactly as written; it is only reduced when converted.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct FcpxmlTime {
+    numerator: i64,
+    denominator: u64,
+}
+
+impl FcpxmlTime {
+    /// Zero seconds, printed as `0s`.
+    pub const ZERO: Self = Self {
+        numerator: 0,
+        denominator: 1,
+    };
+
+    /// Create a new time of `numerator / denominator` seconds.
+    ///
+    /// Returns `None` if the `denominator` is zero.
+    #[inline]
+    pub fn new(numerator: i64, denominator: u64) -> Option<Self> {
+        (0 != denominator).then_some(Self {
+            numerator,
+            denominator,
+        })
+    }
+
+    /// The numerator, in seconds.
+    #[inline]
+    pub fn numerator(&self) -> i64 {
+        self.numerator
+    }
+
+    /// The denominator.
+    #[inline]
+    pub fn denominator(&self) -> u64 {
+        self.denominator
+    }
+
+    /// Express `tick` as a reduced fraction of seconds.
+    pub fn from_tick(tick: Tick) -> Self {
+        let divisor = gcd(tick.0.unsigned_abs(), TICKS_PER_SECOND as u64);
+
+        Self {
+            numerator: tick.0 / divisor as i64,
+            denominator: TICKS_PER_SECOND as u64 / divisor,
+        }
+    }
+
+    /// Express `tick` in multiples of the frame duration of `frame_rate`,
+    /// the way Final Cut Pro writes it (e.g. `3003/30000s`).
+    ///
+    /// This is used if `tick` is the first tick at or after a frame boundary.
+    /// Otherwise this falls back to [`from_tick()`](Self::from_tick).
+    pub fn from_tick_at(tick: Tick, frame_rate: FrameRate) -> Self {
+        let num = frame_rate.num() as i128;
+        let den = frame_rate.den() as i128;
+
+        // The last frame starting at or before the tick:
+        // tick * num / (TICKS_PER_SECOND * den), rounded down.
+        let divisor = TICKS_PER_SECOND as i128 * den;
+        let frames = div_round(tick.0 as i128 * num, divisor, Rounding::Floor);
+
+        if frame_start(frames, frame_rate) != tick.0 as i128 {
+            return Self::from_tick(tick);
+        }
+        match i64::try_from(frames * den) {
+            Ok(numerator) => Self {
+                numerator,
+                denominator: num as _,
+            },
+            Err(_) => Self::from_tick(tick),
+        }
+    }
+
+    /// Convert to a [`Tick`], failing if this is not an integral number of
+    /// ticks.
+    pub fn to_tick(self) -> Result<Tick, FcpxmlError> {
+        let numer = self.numerator as i128 * TICKS_PER_SECOND as i128;
+        let denom = self.denominator as i128;
+
+        if 0 == numer % denom {
+            i64::try_from(numer / denom)
+                .map(Tick)
+                .map_err(|_| FcpxmlError::Overflow)
+        } else {
+            Err(FcpxmlError::Inexact {
+                nearest: Tick(div_round(numer, denom, Rounding::Nearest) as _),
+            })
+        }
+    }
+
+    /// The frame rate this time is the frame duration of.
+    pub fn to_frame_rate(self) -> Result<FrameRate, FcpxmlError> {
+        if self.numerator <= 0 {
+            return Err(FcpxmlError::InvalidFrameDuration);
+        }
+
+        let divisor = gcd(self.numerator as u64, self.denominator);
+        let (num, den) = (
+            u32::try_from(self.denominator / divisor),
+            u32::try_from(self.numerator as u64 / divisor),
+        );
+
+        match (num, den) {
+            (Ok(num), Ok(den)) => FrameRate::new(num, den),
+            _ => None,
+        }
+        .ok_or(FcpxmlError::InvalidFrameDuration)
+    }
+}
+
+impl Default for FcpxmlTime {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl Display for FcpxmlTime {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if 1 == self.denominator {
+            write!(f, "{}s", self.numerator)
+        } else {
+            write!(f, "{}/{}s", self.numerator, self.denominator)
+        }
+    }
+}
+
+impl FromStr for FcpxmlTime {
+    type Err = FcpxmlError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.strip_suffix('s').ok_or(FcpxmlError::Syntax)?;
+        let (numerator, denominator) = s.split_once('/').unwrap_or((s, "1"));
+
+        let (negative, numerator) = match numerator.strip_prefix('-') {
+            Some(numerator) => (true, numerator),
+            None => (false, numerator),
+        };
+        let numerator = parse_digits(numerator)
+            .and_then(|value| i64::try_from(value).ok())
+            .ok_or(FcpxmlError::Syntax)?;
+        let numerator = if negative { -numerator } else { numerator };
+        let denominator =
+            parse_digits(denominator).ok_or(FcpxmlError::Syntax)?;
+
+        Self::new(numerator, denominator).ok_or(FcpxmlError::ZeroDenominator)
+    }
+}
+
+impl TryFrom<FcpxmlTime> for Tick {
+    type Error = FcpxmlError;
+
+    fn try_from(time: FcpxmlTime) -> Result<Self, Self::Error> {
+        time.to_tick()
+    }
+}
+
+impl From<Tick> for FcpxmlTime {
+    fn from(tick: Tick) -> Self {
+        Self::from_tick(tick)
+    }
+}
+
+impl Tick {
+    /// Parse an FCPXML time value like `"3003/30000s"` or `"10s"`.
+    ///
+    /// Fails if the value is not an integral number of ticks.
+    pub fn from_fcpxml(s: &str) -> Result<Self, FcpxmlError> {
+        s.parse::<FcpxmlTime>()?.to_tick()
+    }
+
+    /// Express this tick as an FCPXML time value.
+    ///
+    /// If a `frame_rate` is given and this tick is on its frame grid, the
+    /// value is written in multiples of the frame duration.
+    pub fn to_fcpxml(self, frame_rate: Option<FrameRate>) -> FcpxmlTime {
+        match frame_rate {
+            Some(frame_rate) => FcpxmlTime::from_tick_at(self, frame_rate),
+            None => FcpxmlTime::from_tick(self),
+        }
+    }
+}
+
+impl FrameRate {
+    /// Parse an FCPXML `frameDuration` like `"1001/30000s"`.
+    pub fn from_frame_duration(s: &str) -> Result<Self, FcpxmlError> {
+        s.parse::<FcpxmlTime>()?.to_frame_rate()
+    }
+
+    /// The duration of a single frame as an FCPXML time value.
+    pub fn frame_duration(self) -> FcpxmlTime {
+        FcpxmlTime {
+            numerator: self.den() as _,
+            denominator: self.num() as _,
+        }
+    }
+}
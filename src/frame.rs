@@ -0,0 +1,433 @@
+//! Frame numbers tagged with their frame rate.
+//!
+//! A [`Frame<R>`] can only be combined with frames of the same rate `R`.
+//! `R` is either a zero-sized marker like [`Film`] or [`Ntsc`], for rates
+//! known at compile time, or a [`FrameRate`], for rates only known at
+//! runtime.
+//!
+//! # Examples
+//!
+//! ```
+//! use frame_tick::{
+//!     FrameRate, Rounding, Tick,
+//!     frame::{Film, Frame, Pal},
+//! };
+//!
+//! let frame = Frame::<Film>::new(48);
+//! assert_eq!(Tick::from_secs(2.0), frame.to_tick());
+//! assert_eq!(Frame::new(50), frame.to_rate(Pal, Rounding::Floor));
+//!
+//! // Frames at different rates do not mix.
+//! // let sum = frame + Frame::<Pal>::new(1); // Does not compile.
+//!
+//! // The rate can also be chosen at runtime.
+//! let frame = Frame::with_rate(48, FrameRate::FILM);
+//! assert_eq!(Tick::from_secs(2.0), Tick::from(frame));
+//! ```
+//!
+//! Positions inside a frame, e.g. for motion blur or simulation substeps,
+//! are a [`FramePosition`] or a fractional frame number:
+//!
+//! ```
+//! use frame_tick::{FrameRate, Tick, frame::FramePosition};
+//!
+//! let tick = Tick::from_frames_f64(12.5, FrameRate::FILM);
+//! assert_eq!(12.5, tick.to_frames_f64(FrameRate::FILM));
+//!
+//! let position = FramePosition::from_tick(tick, FrameRate::FILM);
+//! assert_eq!(12, position.frame);
+//! assert_eq!(tick, position.to_tick(FrameRate::FILM));
+//! ```
+use crate::{
+    FrameRate, FrameRateConversion, Rounding, TICKS_PER_SECOND, Tick, div_round,
+};
+use core::ops::{Add, AddAssign, Neg, Range, Sub, SubAssign};
+
+/// A frame rate, known at compile time or at runtime.
+pub trait Rate: Copy + Eq {
+    /// The frame rate.
+    fn frame_rate(self) -> FrameRate;
+}
+
+impl Rate for FrameRate {
+    #[inline]
+    fn frame_rate(self) -> FrameRate {
+        self
+    }
+}
+
+macro_rules! rate_markers {
+    ($($name:ident => $frame_rate:ident;)*) => {
+        $(
+            #[doc = concat!("Marker for [`FrameRate::", stringify!($frame_rate), "`].")]
+            #[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+            pub struct $name;
+
+            impl Rate for $name {
+                #[inline]
+                fn frame_rate(self) -> FrameRate {
+                    FrameRate::$frame_rate
+                }
+            }
+        )*
+    };
+}
+
+rate_markers! {
+    Film => FILM;
+    Fps30 => FPS_30;
+    Fps60 => FPS_60;
+    Ntsc => NTSC;
+    NtscFilm => NTSC_FILM;
+    NtscHigh => NTSC_HIGH;
+    Pal => PAL;
+    PalHigh => PAL_HIGH;
+    Fps12_5 => FPS_12_5;
+    Fps14_985 => FPS_14_985;
+    Fps15 => FPS_15;
+    Fps47_952 => FPS_47_952;
+    Fps48 => FPS_48;
+    Fps72 => FPS_72;
+    Fps90 => FPS_90;
+    Fps96 => FPS_96;
+    Fps100 => FPS_100;
+    Fps119_88 => FPS_119_88;
+    Fps120 => FPS_120;
+    Fps144 => FPS_144;
+    Fps165 => FPS_165;
+    Fps240 => FPS_240;
+}
+
+/// The first tick of `frame` at `rate`.
+///
+/// A tick belongs to the frame whose exact start is at or before it. So if
+/// a frame does not start on an integral tick, its first tick is the one
+/// after its exact start.
+///
+/// # Example
+/// ```
+/// use frame_tick::{
+///     Tick,
+///     frame::{Ntsc, frame_containing, frame_end, frame_start},
+/// };
+///
+/// let start = frame_start(1, Ntsc);
+/// assert_eq!(1, frame_containing(start, Ntsc));
+/// assert_eq!(0, frame_containing(start - Tick::new(1), Ntsc));
+/// assert_eq!(frame_start(2, Ntsc), frame_end(1, Ntsc));
+/// ```
+#[inline]
+pub fn frame_start<R: Rate>(frame: i64, rate: R) -> Tick {
+    Tick(crate::frame_start(frame as _, rate.frame_rate()) as _)
+}
+
+/// The tick after the last tick of `frame` at `rate`, i.e. the first tick
+/// of the next frame.
+#[inline]
+pub fn frame_end<R: Rate>(frame: i64, rate: R) -> Tick {
+    Tick(crate::frame_start(frame as i128 + 1, rate.frame_rate()) as _)
+}
+
+/// The ticks that belong to `frame` at `rate`.
+#[inline]
+pub fn frame_range<R: Rate>(frame: i64, rate: R) -> Range<Tick> {
+    frame_start(frame, rate)..frame_end(frame, rate)
+}
+
+/// The number of ticks that belong to `frame` at `rate`.
+///
+/// At rates that are not [exact](FrameRate::is_exact) this differs by up to
+/// one tick between frames, e.g. 120,240 or 120,241 ticks at 29.97 fps.
+#[inline]
+pub fn frame_duration<R: Rate>(frame: i64, rate: R) -> Tick {
+    frame_end(frame, rate) - frame_start(frame, rate)
+}
+
+/// The frame `tick` belongs to at `rate`.
+#[inline]
+pub fn frame_containing<R: Rate>(tick: Tick, rate: R) -> i64 {
+    let frame_rate = rate.frame_rate();
+    div_round(
+        tick.0 as i128 * frame_rate.num() as i128,
+        TICKS_PER_SECOND as i128 * frame_rate.den() as i128,
+        Rounding::Floor,
+    ) as _
+}
+
+impl<R: Rate> FrameRateConversion<R> for Tick {
+    /// The number of the frame containing this tick, see
+    /// [`frame_containing()`].
+    #[inline]
+    fn to_frames(self, frame_rate: R) -> i64 {
+        frame_containing(self, frame_rate)
+    }
+
+    /// The first tick of frame number `frames`, see [`frame_start()`].
+    #[inline]
+    fn from_frames(frames: i64, frame_rate: R) -> Self {
+        frame_start(frames, frame_rate)
+    }
+}
+
+/// A frame number at frame rate `R`.
+///
+/// Arithmetic is only defined between frames of the same rate. With a
+/// runtime [`FrameRate`] as `R`, mixing frames of different rates panics.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Frame<R> {
+    index: i64,
+    rate: R,
+}
+
+impl<R: Rate + Default> Frame<R> {
+    /// Frame number `index` at the rate of marker `R`.
+    #[inline]
+    pub fn new(index: i64) -> Self {
+        Self::with_rate(index, R::default())
+    }
+
+    /// The frame containing `tick` at the rate of marker `R`.
+    #[inline]
+    pub fn from_tick(tick: Tick) -> Self {
+        Self::from_tick_at(tick, R::default())
+    }
+}
+
+impl<R: Rate> Frame<R> {
+    /// Frame number `index` at `rate`.
+    #[inline]
+    pub fn with_rate(index: i64, rate: R) -> Self {
+        Self { index, rate }
+    }
+
+    /// The frame containing `tick` at `rate`.
+    #[inline]
+    pub fn from_tick_at(tick: Tick, rate: R) -> Self {
+        Self::with_rate(tick.to_frames(rate), rate)
+    }
+
+    /// The frame number.
+    #[inline]
+    pub fn index(self) -> i64 {
+        self.index
+    }
+
+    /// The rate.
+    #[inline]
+    pub fn rate(self) -> R {
+        self.rate
+    }
+
+    /// The frame rate.
+    #[inline]
+    pub fn frame_rate(self) -> FrameRate {
+        self.rate.frame_rate()
+    }
+
+    /// The first tick of this frame.
+    #[inline]
+    pub fn to_tick(self) -> Tick {
+        Tick::from_frames(self.index, self.rate)
+    }
+
+    /// The frame at `rate` that corresponds to this one.
+    ///
+    /// The start of this frame is converted exactly and `rounding` picks the
+    /// frame at `rate` if it does not start at the same time.
+    /// [`Rounding::Floor`] gives the frame containing this frame's start.
+    pub fn to_rate<S: Rate>(self, rate: S, rounding: Rounding) -> Frame<S> {
+        let (from, to) = (self.frame_rate(), rate.frame_rate());
+        Frame::with_rate(
+            div_round(
+                self.index as i128 * to.num() as i128 * from.den() as i128,
+                from.num() as i128 * to.den() as i128,
+                rounding,
+            ) as _,
+            rate,
+        )
+    }
+
+    #[inline]
+    fn same_rate(self, other: Self) -> R {
+        assert!(
+            self.rate == other.rate,
+            "frames at different rates can not be combined"
+        );
+        self.rate
+    }
+}
+
+impl<R: Rate> From<Frame<R>> for Tick {
+    #[inline]
+    fn from(frame: Frame<R>) -> Self {
+        frame.to_tick()
+    }
+}
+
+impl<R: Rate> Add for Frame<R> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::with_rate(self.index + rhs.index, self.same_rate(rhs))
+    }
+}
+
+impl<R: Rate> Sub for Frame<R> {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::with_rate(self.index - rhs.index, self.same_rate(rhs))
+    }
+}
+
+impl<R: Rate> Add<i64> for Frame<R> {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: i64) -> Self::Output {
+        Self::with_rate(self.index + rhs, self.rate)
+    }
+}
+
+impl<R: Rate> Sub<i64> for Frame<R> {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: i64) -> Self::Output {
+        Self::with_rate(self.index - rhs, self.rate)
+    }
+}
+
+impl<R: Rate> AddAssign for Frame<R> {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<R: Rate> SubAssign for Frame<R> {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<R: Rate> AddAssign<i64> for Frame<R> {
+    #[inline]
+    fn add_assign(&mut self, rhs: i64) {
+        *self = *self + rhs;
+    }
+}
+
+impl<R: Rate> SubAssign<i64> for Frame<R> {
+    #[inline]
+    fn sub_assign(&mut self, rhs: i64) {
+        *self = *self - rhs;
+    }
+}
+
+impl<R: Rate> Neg for Frame<R> {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Self::with_rate(-self.index, self.rate)
+    }
+}
+
+/// A position inside a frame: the first tick of `frame` plus `offset`.
+///
+/// [`from_tick()`](Self::from_tick) decomposes a tick exactly, at every
+/// rate, such that `0 <= offset` and the position lies inside `frame`.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct FramePosition {
+    /// The frame number.
+    pub frame: i64,
+    /// The offset from the first tick of `frame`.
+    pub offset: Tick,
+}
+
+impl FramePosition {
+    /// Decompose `tick` into the frame containing it and the offset from
+    /// that frame's first tick.
+    #[inline]
+    pub fn from_tick<R: Rate>(tick: Tick, rate: R) -> Self {
+        let frame = tick.to_frames(rate);
+
+        Self {
+            frame,
+            offset: tick - Tick::from_frames(frame, rate),
+        }
+    }
+
+    /// The tick at this position.
+    #[inline]
+    pub fn to_tick<R: Rate>(self, rate: R) -> Tick {
+        Tick::from_frames(self.frame, rate) + self.offset
+    }
+
+    /// The fractional frame number of this position.
+    #[inline]
+    pub fn to_frames_f64<R: Rate>(self, rate: R) -> f64 {
+        self.to_tick(rate).to_frames_f64(rate)
+    }
+
+    /// Move whole frames from `offset` into `frame`, so that the offset
+    /// lies inside the frame.
+    #[inline]
+    pub fn normalize<R: Rate>(self, rate: R) -> Self {
+        Self::from_tick(self.to_tick(rate), rate)
+    }
+}
+
+/// The resolution of the fractional part of frame numbers in
+/// [`Tick::from_frames_f64()`], as a power of two.
+const FRACTION_BITS: u32 = 32;
+
+impl Tick {
+    /// The fractional frame number of this tick, e.g. `12.25`.
+    ///
+    /// The whole frame number is the frame containing this tick, as with
+    /// [`FrameRateConversion::to_frames()`].
+    pub fn to_frames_f64<R: Rate>(self, rate: R) -> f64 {
+        let frame_rate = rate.frame_rate();
+        let numer = self.0 as i128 * frame_rate.num() as i128;
+        let denom = TICKS_PER_SECOND as i128 * frame_rate.den() as i128;
+        let frame = div_round(numer, denom, Rounding::Floor);
+
+        frame as f64 + (numer - frame * denom) as f64 / denom as f64
+    }
+
+    /// The first tick at or after fractional frame number `frames`.
+    ///
+    /// Whole frame numbers give the first tick of the frame, as with
+    /// [`FrameRateConversion::from_frames()`]. Frame numbers beyond the range
+    /// of [`Tick`] saturate, `NaN` gives zero.
+    pub fn from_frames_f64<R: Rate>(frames: f64, rate: R) -> Self {
+        let frame_rate = rate.frame_rate();
+        let whole = frames as i64;
+        let fraction =
+            ((frames - whole as f64) * (1u64 << FRACTION_BITS) as f64) as i128;
+
+        let numer = ((whole as i128) << FRACTION_BITS)
+            .checked_add(fraction)
+            .and_then(|numer| {
+                numer.checked_mul(
+                    TICKS_PER_SECOND as i128 * frame_rate.den() as i128,
+                )
+            });
+        let ticks = match numer {
+            Some(numer) => div_round(
+                numer,
+                (frame_rate.num() as i128) << FRACTION_BITS,
+                Rounding::Ceil,
+            ),
+            None if frames < 0.0 => i128::MIN,
+            None => i128::MAX,
+        };
+
+        Self(ticks.clamp(i64::MIN as _, i64::MAX as _) as _)
+    }
+}
@@ -0,0 +1,387 @@
+//! Sets of disjoint tick ranges and maps from tick ranges to values.
+//!
+//! An [`IntervalSet`] keeps its ranges sorted and merges ranges that overlap
+//! or touch, so membership is a binary search. An [`IntervalMap`] does the
+//! same for ranges carrying a value, merging touching ranges with equal
+//! values.
+//!
+//! # Examples
+//!
+//! Which frames still need rendering:
+//!
+//! ```
+//! use frame_tick::{FrameRate, interval::IntervalSet};
+//!
+//! let mut cache = IntervalSet::new();
+//! cache.insert_frames(0..10, FrameRate::NTSC);
+//! cache.insert_frames(20..30, FrameRate::NTSC);
+//! cache.insert_frames(25..40, FrameRate::NTSC);
+//! assert_eq!(2, cache.ranges().len());
+//!
+//! assert_eq!(
+//!     [10..20, 40..50],
+//!     cache.missing_frames(5..50, FrameRate::NTSC)[..]
+//! );
+//! ```
+//!
+//! Which parts of a timeline are dirty, and why:
+//!
+//! ```
+//! use frame_tick::{Tick, interval::IntervalMap};
+//!
+//! let second = Tick::from_secs_int(1);
+//! let mut dirty = IntervalMap::new();
+//! dirty.insert(Tick::new(0)..second * 2, "edit");
+//! dirty.insert(second..second * 3, "grade");
+//! dirty.insert(second * 3..second * 4, "grade");
+//!
+//! assert_eq!(Some(&"edit"), dirty.get(Tick::new(0)));
+//! assert_eq!(Some(&"grade"), dirty.get(second));
+//! // Touching ranges with equal values are merged.
+//! assert_eq!(2, dirty.len());
+//! ```
+use crate::{
+    Tick,
+    frame::{Rate, frame_containing, frame_start},
+};
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// A set of ticks, stored as sorted, disjoint ranges.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct IntervalSet {
+    /// Sorted, non-empty and neither overlapping nor touching.
+    ranges: Vec<Range<Tick>>,
+}
+
+impl IntervalSet {
+    /// An empty set.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { ranges: Vec::new() }
+    }
+
+    /// The ranges of the set, in ascending order.
+    #[inline]
+    pub fn ranges(&self) -> &[Range<Tick>] {
+        &self.ranges
+    }
+
+    /// Returns `true` if the set contains no ticks.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Removes all ranges.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.ranges.clear();
+    }
+
+    /// Returns `true` if `tick` is in the set.
+    #[inline]
+    pub fn contains(&self, tick: Tick) -> bool {
+        let index = self.ranges.partition_point(|range| range.end <= tick);
+        self.ranges
+            .get(index)
+            .is_some_and(|range| range.start <= tick)
+    }
+
+    /// Returns `true` if all of `range` is in the set.
+    pub fn contains_range(&self, range: Range<Tick>) -> bool {
+        if range.is_empty() {
+            return true;
+        }
+        let index = self.ranges.partition_point(|r| r.end <= range.start);
+        self.ranges
+            .get(index)
+            .is_some_and(|r| r.start <= range.start && range.end <= r.end)
+    }
+
+    /// Returns `true` if any of `range` is in the set.
+    pub fn intersects(&self, range: Range<Tick>) -> bool {
+        let index = self.ranges.partition_point(|r| r.end <= range.start);
+        !range.is_empty()
+            && self.ranges.get(index).is_some_and(|r| r.start < range.end)
+    }
+
+    /// Adds `range` to the set.
+    pub fn insert(&mut self, range: Range<Tick>) {
+        if range.is_empty() {
+            return;
+        }
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+        let mut merged = range;
+        if first < last {
+            merged.start = merged.start.min(self.ranges[first].start);
+            merged.end = merged.end.max(self.ranges[last - 1].end);
+        }
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    /// Removes `range` from the set.
+    pub fn remove(&mut self, range: Range<Tick>) {
+        if range.is_empty() {
+            return;
+        }
+        let first = self.ranges.partition_point(|r| r.end <= range.start);
+        let last = self.ranges.partition_point(|r| r.start < range.end);
+        if last <= first {
+            return;
+        }
+        let head = self.ranges[first].start..range.start;
+        let tail = range.end..self.ranges[last - 1].end;
+        self.ranges.splice(
+            first..last,
+            [head, tail].into_iter().filter(|r| !r.is_empty()),
+        );
+    }
+
+    /// The ticks in `self` or `other`.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut union = self.clone();
+        union.extend(other.ranges.iter().cloned());
+        union
+    }
+
+    /// The ticks in `self` but not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut difference = self.clone();
+        for range in &other.ranges {
+            difference.remove(range.clone());
+        }
+        difference
+    }
+
+    /// The ticks in both `self` and `other`.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut a, mut b) = (self.ranges.iter(), other.ranges.iter());
+        let (mut x, mut y) = (a.next(), b.next());
+        while let (Some(r), Some(s)) = (x, y) {
+            let range = r.start.max(s.start)..r.end.min(s.end);
+            if !range.is_empty() {
+                ranges.push(range);
+            }
+            if r.end < s.end {
+                x = a.next();
+            } else {
+                y = b.next();
+            }
+        }
+        Self { ranges }
+    }
+
+    /// The ticks in `bounds` that are not in the set.
+    pub fn complement(&self, bounds: Range<Tick>) -> Self {
+        let mut complement = Self::new();
+        complement.insert(bounds);
+        complement.difference(self)
+    }
+
+    /// Adds the ticks of `frames` at `rate`.
+    #[inline]
+    pub fn insert_frames<R: Rate>(&mut self, frames: Range<i64>, rate: R) {
+        self.insert(
+            frame_start(frames.start, rate)..frame_start(frames.end, rate),
+        );
+    }
+
+    /// The frames in `frames` at `rate` that are not entirely in the set,
+    /// as sorted, disjoint ranges.
+    pub fn missing_frames<R: Rate>(
+        &self,
+        frames: Range<i64>,
+        rate: R,
+    ) -> Vec<Range<i64>> {
+        let bounds =
+            frame_start(frames.start, rate)..frame_start(frames.end, rate);
+        let mut missing: Vec<Range<i64>> = Vec::new();
+        for gap in self.complement(bounds).ranges {
+            let start = frame_containing(gap.start, rate);
+            let end = frame_containing(gap.end - Tick(1), rate) + 1;
+            match missing.last_mut() {
+                Some(last) if start <= last.end => last.end = end,
+                _ => missing.push(start..end),
+            }
+        }
+        missing
+    }
+}
+
+impl Extend<Range<Tick>> for IntervalSet {
+    fn extend<I: IntoIterator<Item = Range<Tick>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+impl FromIterator<Range<Tick>> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Range<Tick>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+/// A map from ticks to values, stored as sorted, disjoint ranges.
+///
+/// Touching ranges with equal values are merged.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct IntervalMap<V> {
+    /// Sorted, non-empty and not overlapping.
+    entries: Vec<(Range<Tick>, V)>,
+}
+
+impl<V> Default for IntervalMap<V> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V> IntervalMap<V> {
+    /// An empty map.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// The number of ranges.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the map contains no ranges.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes all ranges.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// The ranges and their values, in ascending order.
+    #[inline]
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (Range<Tick>, &V)> {
+        self.entries
+            .iter()
+            .map(|(range, value)| (range.clone(), value))
+    }
+
+    /// The value at `tick`.
+    pub fn get(&self, tick: Tick) -> Option<&V> {
+        let index = self.entries.partition_point(|(r, _)| r.end <= tick);
+        self.entries
+            .get(index)
+            .filter(|(r, _)| r.start <= tick)
+            .map(|(_, value)| value)
+    }
+
+    /// The ranges and values overlapping `range`, clipped to it.
+    pub fn overlapping(
+        &self,
+        range: Range<Tick>,
+    ) -> impl DoubleEndedIterator<Item = (Range<Tick>, &V)> {
+        let first = self.entries.partition_point(|(r, _)| r.end <= range.start);
+        let last = self.entries.partition_point(|(r, _)| r.start < range.end);
+        self.entries[first..last.max(first)]
+            .iter()
+            .map(move |(r, value)| {
+                (r.start.max(range.start)..r.end.min(range.end), value)
+            })
+    }
+
+    /// The ranges of the map, as a set.
+    pub fn to_set(&self) -> IntervalSet {
+        self.entries
+            .iter()
+            .map(|(range, _)| range.clone())
+            .collect()
+    }
+}
+
+impl<V: Clone> IntervalMap<V> {
+    /// Removes `range` from the map.
+    #[inline]
+    pub fn remove(&mut self, range: Range<Tick>) {
+        self.clear_range(&range);
+    }
+
+    /// Removes `range`, splitting entries at its ends, and returns the index
+    /// where it was.
+    fn clear_range(&mut self, range: &Range<Tick>) -> usize {
+        let first = self.entries.partition_point(|(r, _)| r.end <= range.start);
+        if range.is_empty() {
+            return first;
+        }
+        let last = self.entries.partition_point(|(r, _)| r.start < range.end);
+        if last <= first {
+            return first;
+        }
+        let (head, value) = &self.entries[first];
+        let head = (head.start..range.start, value.clone());
+        let (tail, value) = &self.entries[last - 1];
+        let tail = (range.end..tail.end, value.clone());
+        let head_is_empty = head.0.is_empty();
+        self.entries.splice(
+            first..last,
+            [head, tail].into_iter().filter(|(r, _)| !r.is_empty()),
+        );
+        first + usize::from(!head_is_empty)
+    }
+}
+
+impl<V: Clone + PartialEq> IntervalMap<V> {
+    /// Sets the value of `range`, replacing any values there.
+    pub fn insert(&mut self, range: Range<Tick>, value: V) {
+        if range.is_empty() {
+            return;
+        }
+        let mut index = self.clear_range(&range);
+        let mut entry = (range, value);
+        // Merge with touching neighbors of equal value.
+        if let Some((next, value)) = self.entries.get(index)
+            && next.start == entry.0.end
+            && *value == entry.1
+        {
+            entry.0.end = next.end;
+            self.entries.remove(index);
+        }
+        if let Some((previous, value)) =
+            index.checked_sub(1).and_then(|i| self.entries.get(i))
+            && previous.end == entry.0.start
+            && *value == entry.1
+        {
+            entry.0.start = previous.start;
+            index -= 1;
+            self.entries.remove(index);
+        }
+        self.entries.insert(index, entry);
+    }
+}
+
+impl<V: Clone + PartialEq> Extend<(Range<Tick>, V)> for IntervalMap<V> {
+    fn extend<I: IntoIterator<Item = (Range<Tick>, V)>>(&mut self, iter: I) {
+        for (range, value) in iter {
+            self.insert(range, value);
+        }
+    }
+}
+
+impl<V: Clone + PartialEq> FromIterator<(Range<Tick>, V)> for IntervalMap<V> {
+    fn from_iter<I: IntoIterator<Item = (Range<Tick>, V)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        map.extend(iter);
+        map
+    }
+}
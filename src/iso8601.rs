@@ -0,0 +1,272 @@
+//! ISO 8601 durations, e.g. `PT1H2M3.5S`.
+//!
+//! Only durations of fixed length are supported: weeks, days, hours, minutes
+//! and seconds. A day is taken to be 86,400 seconds. Years and months have
+//! no fixed length and are rejected.
+//!
+//! Parsing and formatting is exact. Fractions that do not map to an integral
+//! number of ticks are rejected on parsing, and ticks that do not have a
+//! terminating decimal representation in seconds (e.g. one frame at 24 fps)
+//! can not be formatted.
+//!
+//! # Examples
+//!
+//! ```
+//! use frame_tick::{Tick, iso8601::Iso8601Duration};
+//!
+//! let tick = Tick::from_iso8601("PT1H2M3.5S").unwrap();
+//! assert_eq!(Tick::from_secs(3723.5), tick);
+//! assert_eq!("PT1H2M3.5S", tick.to_iso8601().unwrap().to_string());
+//! ```
+use crate::{Rounding, TICKS_PER_SECOND, Tick, div_round, gcd, parse_digits};
+use core::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+/// An error parsing or formatting an ISO 8601 duration.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Iso8601Error {
+    /// The string is not an ISO 8601 duration.
+    Syntax,
+    /// The duration contains years or months, which have no fixed length.
+    Nominal,
+    /// The duration is not an integral number of ticks. Carries the nearest
+    /// [`Tick`].
+    Inexact {
+        /// The nearest tick to the requested duration.
+        nearest: Tick,
+    },
+    /// The tick has no terminating decimal representation in seconds.
+    NonTerminating,
+    /// The result does not fit into a [`Tick`].
+    Overflow,
+}
+
+impl Display for Iso8601Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Syntax => write!(f, "malformed ISO 8601 duration"),
+            Self::Nominal => write!(
+                f,
+                "ISO 8601 durations with years or months have no fixed length"
+            ),
+            Self::Inexact { nearest } => write!(
+                f,
+                "ISO 8601 duration is not an integral number of ticks \
+                 (nearest: {})",
+                nearest.0
+            ),
+            Self::NonTerminating => write!(
+                f,
+                "tick has no terminating decimal representation in seconds"
+            ),
+            Self::Overflow => write!(f, "ISO 8601 duration overflows a tick"),
+        }
+    }
+}
+
+impl core::error::Error for Iso8601Error {}
+
+/// A [`Tick`] that can be written as an exact ISO 8601 duration.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Iso8601Duration(Tick);
+
+impl Iso8601Duration {
+    /// Wrap `tick`, failing if its seconds have no terminating decimal
+    /// representation.
+    pub fn new(tick: Tick) -> Result<Self, Iso8601Error> {
+        if is_decimal(tick) {
+            Ok(Self(tick))
+        } else {
+            Err(Iso8601Error::NonTerminating)
+        }
+    }
+
+    /// The wrapped tick.
+    #[inline]
+    pub fn tick(self) -> Tick {
+        self.0
+    }
+}
+
+impl Display for Iso8601Duration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let ticks = self.0.0.unsigned_abs();
+        let seconds = ticks / TICKS_PER_SECOND as u64;
+        let fraction = ticks % TICKS_PER_SECOND as u64;
+
+        if self.0.0 < 0 {
+            f.write_str("-")?;
+        }
+        f.write_str("PT")?;
+        if 3600 <= seconds {
+            write!(f, "{}H", seconds / 3600)?;
+        }
+        if !(seconds / 60).is_multiple_of(60) {
+            write!(f, "{}M", seconds / 60 % 60)?;
+        }
+        if !seconds.is_multiple_of(60) || 0 != fraction || 0 == seconds {
+            write!(f, "{}", seconds % 60)?;
+            write_fraction(f, fraction)?;
+            f.write_str("S")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Iso8601Duration {
+    type Err = Iso8601Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, s) = match s.strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let s = s.strip_prefix('P').ok_or(Iso8601Error::Syntax)?;
+        let (date, time) = match s.split_once('T') {
+            Some((_, "")) => return Err(Iso8601Error::Syntax),
+            Some((date, time)) => (date, Some(time)),
+            None => (s, None),
+        };
+        if date.is_empty() && time.is_none() {
+            return Err(Iso8601Error::Syntax);
+        }
+
+        let mut ticks = 0i128;
+        // Only the smallest component may have a fraction.
+        let mut fractional = false;
+        for (part, designators) in [
+            (
+                date,
+                &[('Y', 0), ('M', 0), ('W', 604_800), ('D', 86_400)][..],
+            ),
+            (time.unwrap_or(""), &[('H', 3600), ('M', 60), ('S', 1)][..]),
+        ] {
+            let mut rest = part;
+            let mut designators = designators.iter();
+            while !rest.is_empty() {
+                if fractional {
+                    return Err(Iso8601Error::Syntax);
+                }
+
+                let end = rest
+                    .find(|c: char| c.is_ascii_alphabetic())
+                    .ok_or(Iso8601Error::Syntax)?;
+                let (number, designator) = (&rest[..end], rest.as_bytes()[end]);
+                rest = &rest[end + 1..];
+
+                // Designators must appear in order, each at most once.
+                let &(_, seconds) = designators
+                    .find(|(c, _)| *c as u8 == designator)
+                    .ok_or(Iso8601Error::Syntax)?;
+                if 0 == seconds {
+                    return Err(Iso8601Error::Nominal);
+                }
+
+                let (integer, fraction) =
+                    number.split_once(['.', ',']).unwrap_or((number, ""));
+                let unit = seconds as i128 * TICKS_PER_SECOND as i128;
+                ticks = parse_digits(integer)
+                    .ok_or(Iso8601Error::Syntax)
+                    .and_then(|integer| {
+                        (integer as i128)
+                            .checked_mul(unit)
+                            .and_then(|value| value.checked_add(ticks))
+                            .ok_or(Iso8601Error::Overflow)
+                    })?;
+
+                if number.len() != integer.len() {
+                    fractional = true;
+                    if 18 < fraction.len() {
+                        return Err(Iso8601Error::Syntax);
+                    }
+                    let scale = 10i128.pow(fraction.len() as _);
+                    let numer = parse_digits(fraction)
+                        .ok_or(Iso8601Error::Syntax)?
+                        as i128
+                        * unit;
+
+                    ticks += div_round(numer, scale, Rounding::Nearest);
+                    if 0 != numer % scale {
+                        let ticks = if negative { -ticks } else { ticks };
+                        return Err(Iso8601Error::Inexact {
+                            nearest: Tick(
+                                i64::try_from(ticks)
+                                    .map_err(|_| Iso8601Error::Overflow)?,
+                            ),
+                        });
+                    }
+                }
+            }
+        }
+
+        let ticks = if negative { -ticks } else { ticks };
+        i64::try_from(ticks)
+            .map(|ticks| Self(Tick(ticks)))
+            .map_err(|_| Iso8601Error::Overflow)
+    }
+}
+
+impl TryFrom<Tick> for Iso8601Duration {
+    type Error = Iso8601Error;
+
+    fn try_from(tick: Tick) -> Result<Self, Self::Error> {
+        Self::new(tick)
+    }
+}
+
+impl From<Iso8601Duration> for Tick {
+    fn from(duration: Iso8601Duration) -> Self {
+        duration.0
+    }
+}
+
+impl Tick {
+    /// Parse an ISO 8601 duration like `PT1H2M3.5S`.
+    pub fn from_iso8601(s: &str) -> Result<Self, Iso8601Error> {
+        s.parse::<Iso8601Duration>().map(Iso8601Duration::tick)
+    }
+
+    /// Express this tick as an ISO 8601 duration.
+    ///
+    /// Fails if the seconds have no terminating decimal representation.
+    pub fn to_iso8601(self) -> Result<Iso8601Duration, Iso8601Error> {
+        Iso8601Duration::new(self)
+    }
+}
+
+/// Whether the seconds of `tick` have a terminating decimal representation.
+pub(crate) fn is_decimal(tick: Tick) -> bool {
+    let fraction = tick.0.rem_euclid(TICKS_PER_SECOND) as u64;
+    let mut den =
+        TICKS_PER_SECOND as u64 / gcd(fraction, TICKS_PER_SECOND as _);
+    for factor in [2, 5] {
+        while den.is_multiple_of(factor) {
+            den /= factor;
+        }
+    }
+
+    1 == den
+}
+
+/// Write `fraction` ticks as the decimal places of a second, including the
+/// decimal point. Writes nothing if `fraction` is zero.
+///
+/// The caller must ensure this terminates, see [`is_decimal()`].
+pub(crate) fn write_fraction(
+    f: &mut Formatter<'_>,
+    mut fraction: u64,
+) -> fmt::Result {
+    if 0 != fraction {
+        f.write_str(".")?;
+        while 0 != fraction {
+            fraction *= 10;
+            write!(f, "{}", fraction / TICKS_PER_SECOND as u64)?;
+            fraction %= TICKS_PER_SECOND as u64;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,26 @@
+//! Conversions between [`Tick`] and [`jiff::SignedDuration`].
+//!
+//! Both directions round to the nearest nanosecond or tick, see
+//! [`Tick::to_nanos()`].
+use crate::{DurationError, NANOS_PER_SECOND, Rounding, Tick};
+use jiff::SignedDuration;
+
+impl From<Tick> for SignedDuration {
+    /// Every tick is in range of a `SignedDuration`.
+    fn from(tick: Tick) -> Self {
+        let nanos = tick.to_nanos(Rounding::Nearest);
+
+        SignedDuration::new(
+            (nanos / NANOS_PER_SECOND) as _,
+            (nanos % NANOS_PER_SECOND) as _,
+        )
+    }
+}
+
+impl TryFrom<SignedDuration> for Tick {
+    type Error = DurationError;
+
+    fn try_from(duration: SignedDuration) -> Result<Self, Self::Error> {
+        Tick::from_nanos(duration.as_nanos(), Rounding::Nearest)
+    }
+}
@@ -32,6 +32,9 @@
 //!
 //! - And many more.
 //!
+//! Use [`resolution::TickResolution`] to check whether other rates are
+//! covered.
+//!
 //! # Examples
 //!
 //! ```
@@ -50,10 +53,20 @@
 #![doc = document_features::document_features!()]
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 use core::{
+    cmp::Ordering,
     convert::{AsMut, AsRef},
+    fmt::{self, Display, Formatter},
+    hash::{Hash, Hasher},
+    iter::{FusedIterator, Sum},
     num::{NonZeroU32, ParseIntError},
-    ops::{Add, Div, Mul, Sub},
+    ops::{
+        Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Range, Rem,
+        RemAssign, Sub, SubAssign,
+    },
     str::FromStr,
 };
 #[cfg(feature = "facet")]
@@ -61,8 +74,37 @@ use facet::Facet;
 #[cfg(all(feature = "std", doc))]
 use std::time::Duration;
 
+#[cfg(feature = "alloc")]
+pub mod cadence;
+pub mod catalog;
+#[cfg(feature = "chrono")]
+pub mod chrono_traits;
+pub mod fcpxml;
+pub mod frame;
+#[cfg(feature = "alloc")]
+pub mod interval;
+pub mod iso8601;
+#[cfg(feature = "jiff")]
+pub mod jiff_traits;
+#[cfg(feature = "otio")]
+pub mod otio;
+pub mod quantize;
+pub mod resolution;
+pub mod ruler;
+#[cfg(feature = "alloc")]
+pub mod scheduler;
 #[cfg(feature = "std")]
 pub mod std_traits;
+pub mod subtitle;
+#[cfg(feature = "time")]
+pub mod time_traits;
+pub mod timecode;
+#[cfg(feature = "alloc")]
+pub mod timeline;
+#[cfg(feature = "alloc")]
+pub mod vfr;
+#[cfg(feature = "serde")]
+pub mod with;
 
 #[cfg(test)]
 mod tests;
@@ -78,14 +120,25 @@ pub type FramesPerSec = NonZeroU32;
 ///
 /// This allows exact representation of fractional frame rates like NTSC
 /// (30000/1001 ≈ 29.97 fps).
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+///
+/// The fraction is kept in lowest terms. Frame rates compare and order by
+/// their actual rate.
+///
+/// # Example
+/// ```
+/// use frame_tick::FrameRate;
+///
+/// assert_eq!(FrameRate::FILM, FrameRate::new(48, 2).unwrap());
+/// assert!(FrameRate::NTSC < FrameRate::FPS_30);
+/// assert_eq!(FrameRate::NTSC, "29.97".parse().unwrap());
+/// assert_eq!("59.94 (60000/1001)", (FrameRate::NTSC * 2).to_string());
+/// ```
+#[derive(Copy, Clone, Debug)]
 #[cfg_attr(feature = "facet", derive(Facet))]
 #[cfg_attr(feature = "facet", facet(opaque))]
-#[cfg_attr(
-    feature = "rkyv",
-    derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize)
-)]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize))]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawFrameRate"))]
 pub struct FrameRate {
     /// Numerator (frames).
     num: NonZeroU32,
@@ -99,16 +152,86 @@ impl FrameRate {
         num: NonZeroU32::new(24).unwrap(),
         den: NonZeroU32::new(1).unwrap(),
     };
+    /// 100 fps - Double PAL high frame rate.
+    pub const FPS_100: Self = Self {
+        num: NonZeroU32::new(100).unwrap(),
+        den: NonZeroU32::new(1).unwrap(),
+    };
+    /// 119.88 fps (120000/1001) - NTSC 120 fps.
+    pub const FPS_119_88: Self = Self {
+        num: NonZeroU32::new(120000).unwrap(),
+        den: NonZeroU32::new(1001).unwrap(),
+    };
+    /// 120 fps - High refresh rate displays.
+    pub const FPS_120: Self = Self {
+        num: NonZeroU32::new(120).unwrap(),
+        den: NonZeroU32::new(1).unwrap(),
+    };
+    /// 12.5 fps - Half of PAL.
+    pub const FPS_12_5: Self = Self {
+        num: NonZeroU32::new(25).unwrap(),
+        den: NonZeroU32::new(2).unwrap(),
+    };
+    /// 144 fps - Gaming displays.
+    pub const FPS_144: Self = Self {
+        num: NonZeroU32::new(144).unwrap(),
+        den: NonZeroU32::new(1).unwrap(),
+    };
+    /// 14.985 fps (15000/1001) - Half of NTSC.
+    pub const FPS_14_985: Self = Self {
+        num: NonZeroU32::new(15000).unwrap(),
+        den: NonZeroU32::new(1001).unwrap(),
+    };
+    /// 15 fps - Half of 30 fps.
+    pub const FPS_15: Self = Self {
+        num: NonZeroU32::new(15).unwrap(),
+        den: NonZeroU32::new(1).unwrap(),
+    };
+    /// 165 fps - Gaming displays.
+    pub const FPS_165: Self = Self {
+        num: NonZeroU32::new(165).unwrap(),
+        den: NonZeroU32::new(1).unwrap(),
+    };
+    /// 240 fps - Gaming displays, slow motion capture.
+    pub const FPS_240: Self = Self {
+        num: NonZeroU32::new(240).unwrap(),
+        den: NonZeroU32::new(1).unwrap(),
+    };
     /// 30 fps.
     pub const FPS_30: Self = Self {
         num: NonZeroU32::new(30).unwrap(),
         den: NonZeroU32::new(1).unwrap(),
     };
+    /// 47.952 fps (48000/1001) - High frame rate film on NTSC video.
+    pub const FPS_47_952: Self = Self {
+        num: NonZeroU32::new(48000).unwrap(),
+        den: NonZeroU32::new(1001).unwrap(),
+    };
+    /// 48 fps - High frame rate film.
+    pub const FPS_48: Self = Self {
+        num: NonZeroU32::new(48).unwrap(),
+        den: NonZeroU32::new(1).unwrap(),
+    };
     /// 60 fps.
     pub const FPS_60: Self = Self {
         num: NonZeroU32::new(60).unwrap(),
         den: NonZeroU32::new(1).unwrap(),
     };
+    /// 72 fps - Triple-flash film projection, Oculus Quest 1.
+    pub const FPS_72: Self = Self {
+        num: NonZeroU32::new(72).unwrap(),
+        den: NonZeroU32::new(1).unwrap(),
+    };
+    /// 90 fps - VR headsets.
+    pub const FPS_90: Self = Self {
+        num: NonZeroU32::new(90).unwrap(),
+        den: NonZeroU32::new(1).unwrap(),
+    };
+    /// 96 fps - Quadruple film rate.
+    pub const FPS_96: Self = Self {
+        num: NonZeroU32::new(96).unwrap(),
+        den: NonZeroU32::new(1).unwrap(),
+    };
     /// 29.97 fps (30000/1001) - NTSC.
     pub const NTSC: Self = Self {
         num: NonZeroU32::new(30000).unwrap(),
@@ -144,31 +267,473 @@ impl FrameRate {
     /// // 29.97 fps (NTSC)
     /// let ntsc = FrameRate::new(30000, 1001).unwrap();
     /// ```
+    ///
+    /// The fraction is reduced to lowest terms, so `48/2` is `24/1`.
+    #[inline]
+    pub const fn new(num: u32, den: u32) -> Option<Self> {
+        if 0 == num || 0 == den {
+            return None;
+        }
+
+        let divisor = gcd(num as _, den as _) as u32;
+        match (
+            NonZeroU32::new(num / divisor),
+            NonZeroU32::new(den / divisor),
+        ) {
+            (Some(num), Some(den)) => Some(Self { num, den }),
+            _ => None,
+        }
+    }
+
+    /// The frame rate `rhs` times as fast, or `None` if the result does not
+    /// fit.
+    #[inline]
+    pub const fn checked_mul(self, rhs: u32) -> Option<Self> {
+        Self::from_u64(self.num.get() as u64 * rhs as u64, self.den.get() as _)
+    }
+
+    /// The frame rate `rhs` times as slow, or `None` if `rhs` is zero or the
+    /// result does not fit.
     #[inline]
-    pub fn new(num: u32, den: u32) -> Option<Self> {
-        Some(Self {
-            num: NonZeroU32::new(num)?,
-            den: NonZeroU32::new(den)?,
+    pub const fn checked_div(self, rhs: u32) -> Option<Self> {
+        Self::from_u64(self.num.get() as _, self.den.get() as u64 * rhs as u64)
+    }
+
+    /// Reduce `num / den` and create a frame rate from it, if it fits.
+    const fn from_u64(num: u64, den: u64) -> Option<Self> {
+        if 0 == num || 0 == den {
+            return None;
+        }
+
+        let divisor = gcd(num, den);
+        let (num, den) = (num / divisor, den / divisor);
+        if num <= u32::MAX as u64 && den <= u32::MAX as u64 {
+            Self::new(num as _, den as _)
+        } else {
+            None
+        }
+    }
+
+    /// The frame rate as frames per second.
+    #[inline]
+    pub fn to_f64(self) -> f64 {
+        self.num.get() as f64 / self.den.get() as f64
+    }
+
+    /// The frame rate that is exactly `rate` as an `f64`, e.g. `29.97002997`
+    /// for [`NTSC`](Self::NTSC).
+    ///
+    /// This finds the fraction with the smallest denominator (up to 1001)
+    /// that maps to the very same `f64`. Returns `None` if there is none.
+    pub fn from_f64(rate: f64) -> Option<Self> {
+        if !(0.0 < rate && rate <= u32::MAX as f64) {
+            return None;
+        }
+
+        (1..=MAX_FLOAT_RATE_DENOMINATOR).find_map(|den| {
+            let num = rate * den as f64 + 0.5;
+            if num > u32::MAX as f64 {
+                return None;
+            }
+            let num = num as u32;
+            (num as f64 / den as f64 == rate)
+                .then(|| Self::new(num, den))
+                .flatten()
         })
     }
 
+    /// The frame rate closest to `rate` within `tolerance` frames per
+    /// second, preferring standard rates.
+    ///
+    /// Integer rates and their 1000/1001 counterparts (e.g. `23.976`,
+    /// `29.97` or `59.94`) are recognized first. Otherwise this falls back
+    /// to the best rational approximation with a denominator of at most
+    /// 1001, found via continued fractions.
+    ///
+    /// Returns `None` if `rate` is not positive and finite or nothing is
+    /// within `tolerance`.
+    ///
+    /// # Example
+    /// ```
+    /// use frame_tick::FrameRate;
+    ///
+    /// assert_eq!(
+    ///     Some(FrameRate::NTSC),
+    ///     FrameRate::from_f64_snapped(29.97, 0.01)
+    /// );
+    /// assert_eq!(
+    ///     Some(FrameRate::FILM),
+    ///     FrameRate::from_f64_snapped(23.9999, 0.01)
+    /// );
+    /// assert_eq!(
+    ///     FrameRate::new(25, 2),
+    ///     FrameRate::from_f64_snapped(12.5, 0.0)
+    /// );
+    /// assert_eq!(
+    ///     None,
+    ///     FrameRate::from_f64_snapped(core::f64::consts::PI, 0.0)
+    /// );
+    /// ```
+    pub fn from_f64_snapped(rate: f64, tolerance: f64) -> Option<Self> {
+        if !(0.0 < rate && rate <= u32::MAX as f64 && 0.0 <= tolerance) {
+            return None;
+        }
+
+        let error = |frame_rate: Self| (frame_rate.to_f64() - rate).abs();
+        let nominal = (rate * 1.001 + 0.5) as u32;
+        let standard = [
+            Self::new((rate + 0.5) as _, 1),
+            nominal
+                .checked_mul(1000)
+                .and_then(|num| Self::new(num, 1001)),
+        ]
+        .into_iter()
+        .flatten()
+        .filter(|frame_rate| error(*frame_rate) <= tolerance)
+        .min_by(|a, b| error(*a).total_cmp(&error(*b)));
+
+        standard
+            .or_else(|| {
+                best_rational(rate, MAX_FLOAT_RATE_DENOMINATOR as _)
+                    .and_then(|(num, den)| Self::from_u64(num, den))
+            })
+            .filter(|frame_rate| error(*frame_rate) <= tolerance)
+    }
+
     /// Create an integer frame rate (e.g., 24 fps = 24/1).
     #[inline]
-    pub fn from_int(fps: u32) -> Option<Self> {
+    pub const fn from_int(fps: u32) -> Option<Self> {
         Self::new(fps, 1)
     }
 
     /// Get the numerator.
     #[inline]
-    pub fn num(&self) -> u32 {
+    pub const fn num(&self) -> u32 {
         self.num.get()
     }
 
     /// Get the denominator.
     #[inline]
-    pub fn den(&self) -> u32 {
+    pub const fn den(&self) -> u32 {
         self.den.get()
     }
+
+    /// Whether every frame starts on an integral [`Tick`].
+    ///
+    /// This is not the case for e.g. NTSC rates: a frame at 29.97 fps is
+    /// 120,240.12 ticks long. Frame starts at such rates are rounded to the
+    /// next tick.
+    ///
+    /// # Example
+    /// ```
+    /// use frame_tick::FrameRate;
+    ///
+    /// const FPS_120: FrameRate = FrameRate::from_int(120).unwrap();
+    /// const _: () = assert!(FPS_120.is_exact());
+    /// ```
+    #[inline]
+    pub const fn is_exact(&self) -> bool {
+        (TICKS_PER_SECOND as u64 * self.den.get() as u64)
+            .is_multiple_of(self.num.get() as u64)
+    }
+
+    /// Iterate over the frames that start inside `range`, yielding each
+    /// frame number and its first tick.
+    ///
+    /// # Example
+    /// ```
+    /// use frame_tick::{FrameRate, Tick};
+    ///
+    /// let range = Tick::from_secs(1.0)..Tick::from_secs(2.0);
+    /// let frames = FrameRate::NTSC.frames(range);
+    /// assert_eq!(30, frames.len());
+    /// assert_eq!(Some(30), frames.clone().next().map(|(frame, _)| frame));
+    /// assert_eq!(Some(59), frames.last().map(|(frame, _)| frame));
+    /// ```
+    #[inline]
+    pub fn frames(self, range: Range<Tick>) -> FrameIter {
+        FrameIter::new(range, self)
+    }
+}
+
+const _: () = {
+    assert!(FrameRate::FILM.is_exact());
+    assert!(FrameRate::FPS_30.is_exact());
+    assert!(FrameRate::FPS_60.is_exact());
+    assert!(FrameRate::PAL.is_exact());
+    assert!(FrameRate::PAL_HIGH.is_exact());
+};
+
+/// The largest denominator [`FrameRate::from_f64()`] tries.
+///
+/// This covers the 1000/1001 family as well as rates with up to three
+/// decimal places.
+const MAX_FLOAT_RATE_DENOMINATOR: u32 = 1001;
+
+/// The fraction closest to `value` with a denominator of at most
+/// `max_den`, via its continued fraction expansion.
+fn best_rational(value: f64, max_den: u64) -> Option<(u64, u64)> {
+    // Two consecutive convergents, p0/q0 and p1/q1.
+    let (mut p0, mut q0, mut p1, mut q1) = (0u64, 1u64, 1u64, 0u64);
+    let mut rest = value;
+
+    loop {
+        let a = rest as u64;
+        let q2 = a.checked_mul(q1)?.checked_add(q0)?;
+        if max_den < q2 {
+            break;
+        }
+        (p0, q0, p1, q1) = (p1, q1, a.checked_mul(p1)?.checked_add(p0)?, q2);
+
+        let fraction = rest - a as f64;
+        if fraction < f64::EPSILON * rest.max(1.0) {
+            break;
+        }
+        rest = 1.0 / fraction;
+    }
+
+    // The best semiconvergent that still fits may beat the last convergent.
+    let k = (max_den - q0) / q1;
+    let semiconvergent = (p0 + k * p1, q0 + k * q1);
+    let error = |(p, q): (u64, u64)| (p as f64 / q as f64 - value).abs();
+    if error(semiconvergent) < error((p1, q1)) {
+        Some(semiconvergent)
+    } else {
+        Some((p1, q1))
+    }
+}
+
+impl PartialEq for FrameRate {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for FrameRate {}
+
+impl PartialOrd for FrameRate {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FrameRate {
+    /// Orders by the actual rate, so `24000/1001 < 24/1`.
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.num.get() as u64 * other.den.get() as u64)
+            .cmp(&(other.num.get() as u64 * self.den.get() as u64))
+    }
+}
+
+impl Hash for FrameRate {
+    /// Rates are kept in lowest terms, so equal rates hash their fields
+    /// alike.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.num.hash(state);
+        self.den.hash(state);
+    }
+}
+
+/// A [`FrameRate`] as serialized, which may not be in lowest terms.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+#[serde(rename = "FrameRate")]
+struct RawFrameRate {
+    num: u32,
+    den: u32,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<RawFrameRate> for FrameRate {
+    type Error = FrameRateError;
+
+    #[inline]
+    fn try_from(raw: RawFrameRate) -> Result<Self, Self::Error> {
+        Self::new(raw.num, raw.den).ok_or(FrameRateError::Zero)
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl<D> rkyv::Deserialize<FrameRate, D> for ArchivedFrameRate
+where
+    D: rkyv::rancor::Fallible + ?Sized,
+    D::Error: rkyv::rancor::Source,
+{
+    /// Reduces the archived fraction, which may not be in lowest terms.
+    fn deserialize(&self, _: &mut D) -> Result<FrameRate, D::Error> {
+        FrameRate::new(self.num.get(), self.den.get()).ok_or_else(|| {
+            <D::Error as rkyv::rancor::Source>::new(FrameRateError::Zero)
+        })
+    }
+}
+
+impl Mul<u32> for FrameRate {
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// If the result does not fit. See [`FrameRate::checked_mul()`].
+    #[inline]
+    fn mul(self, rhs: u32) -> Self::Output {
+        self.checked_mul(rhs).expect("frame rate overflow")
+    }
+}
+
+impl Div<u32> for FrameRate {
+    type Output = Self;
+
+    /// # Panics
+    ///
+    /// If `rhs` is zero or the result does not fit. See
+    /// [`FrameRate::checked_div()`].
+    #[inline]
+    fn div(self, rhs: u32) -> Self::Output {
+        self.checked_div(rhs)
+            .expect("frame rate division by zero or overflow")
+    }
+}
+
+impl Display for FrameRate {
+    /// Integer rates are written as such, e.g. `24`. Others are written as
+    /// a decimal rounded to three places followed by the exact fraction,
+    /// e.g. `23.976 (24000/1001)`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if 1 == self.den.get() {
+            return write!(f, "{}", self.num);
+        }
+
+        let millis = div_round(
+            self.num.get() as i128 * 1000,
+            self.den.get() as _,
+            Rounding::Nearest,
+        );
+        write!(f, "{}", millis / 1000)?;
+        let mut fraction = millis % 1000;
+        if 0 != fraction {
+            f.write_str(".")?;
+            let mut digits = 3;
+            while 0 == fraction % 10 {
+                fraction /= 10;
+                digits -= 1;
+            }
+            write!(f, "{fraction:0digits$}")?;
+        }
+
+        write!(f, " ({}/{})", self.num, self.den)
+    }
+}
+
+impl FromStr for FrameRate {
+    type Err = FrameRateError;
+
+    /// Parse `24`, `30000/1001` or a decimal like `29.97`.
+    ///
+    /// The [`Display`] form, e.g. `29.97 (30000/1001)`, is read from the
+    /// fraction in parentheses.
+    ///
+    /// Decimals that are a 1000/1001 rate rounded to the given number of
+    /// places are read as that rate, so `29.97` and `23.98` are NTSC rates.
+    /// A trailing `i` denotes an interlaced field rate, so `59.94i` is
+    /// 29.97 frames per second. A trailing `p` is ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (s, fields) = match s.strip_suffix('i') {
+            Some(s) => (s, true),
+            None => (s.strip_suffix('p').unwrap_or(s), false),
+        };
+
+        // The `Display` form.
+        let s = s
+            .strip_suffix(')')
+            .and_then(|s| s.split_once(" ("))
+            .map_or(s, |(_, fraction)| fraction);
+
+        let frame_rate = if let Some((num, den)) = s.split_once('/') {
+            let parse = |s| {
+                parse_digits(s).ok_or(FrameRateError::Syntax).and_then(
+                    |value| {
+                        u32::try_from(value)
+                            .map_err(|_| FrameRateError::Overflow)
+                    },
+                )
+            };
+            Self::new(parse(num)?, parse(den)?).ok_or(FrameRateError::Zero)?
+        } else {
+            parse_decimal_rate(s)?
+        };
+
+        if fields {
+            frame_rate.checked_div(2).ok_or(FrameRateError::Overflow)
+        } else {
+            Ok(frame_rate)
+        }
+    }
+}
+
+/// Parse a decimal frame rate, see [`FrameRate::from_str()`].
+fn parse_decimal_rate(s: &str) -> Result<FrameRate, FrameRateError> {
+    let (integer, fraction) = match s.split_once('.') {
+        Some((_, "")) => return Err(FrameRateError::Syntax),
+        Some((integer, fraction)) => (integer, fraction),
+        None => (s, "0"),
+    };
+    if 9 < fraction.len() {
+        return Err(FrameRateError::Syntax);
+    }
+
+    let scale = 10u64.pow(fraction.len() as _);
+    let (integer, fraction) = parse_digits(integer)
+        .zip(parse_digits(fraction))
+        .ok_or(FrameRateError::Syntax)?;
+    let value = integer
+        .checked_mul(scale)
+        .and_then(|integer| integer.checked_add(fraction))
+        .ok_or(FrameRateError::Overflow)?;
+    if 0 == value {
+        return Err(FrameRateError::Zero);
+    }
+
+    // Is this a 1000/1001 rate written to as many places?
+    let nominal = div_round(
+        value as i128 * 1001,
+        scale as i128 * 1000,
+        Rounding::Nearest,
+    );
+    let (num, den) = if 0 != fraction
+        && value as i128
+            == div_round(
+                nominal * 1000 * scale as i128,
+                1001,
+                Rounding::Nearest,
+            ) {
+        (nominal as u64 * 1000, 1001)
+    } else {
+        (value, scale)
+    };
+
+    FrameRate::from_u64(num, den).ok_or(FrameRateError::Overflow)
+}
+
+#[cfg(feature = "float_frame_rate")]
+impl From<FrameRate> for FramesPerSecF64 {
+    #[inline]
+    fn from(frame_rate: FrameRate) -> Self {
+        // A ratio of two non-zero `u32`s is always positive and finite.
+        Self::new(frame_rate.to_f64()).unwrap()
+    }
+}
+
+#[cfg(feature = "float_frame_rate")]
+impl TryFrom<FramesPerSecF64> for FrameRate {
+    type Error = FrameRateError;
+
+    /// Succeeds if the rate is exactly a fraction with a denominator of at
+    /// most 1001, see [`FrameRate::from_f64()`].
+    fn try_from(frame_rate: FramesPerSecF64) -> Result<Self, Self::Error> {
+        Self::from_f64(frame_rate.get()).ok_or(FrameRateError::Inexact)
+    }
 }
 
 impl From<NonZeroU32> for FrameRate {
@@ -191,6 +756,138 @@ pub const TICKS_PER_SECOND: i64 = 3_603_600;
 #[cfg(feature = "low_res")]
 pub const TICKS_PER_SECOND: i64 = 25_200;
 
+/// How to round a time that falls between two representable values, e.g.
+/// between two frames or two milliseconds.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "facet", derive(Facet))]
+#[cfg_attr(feature = "facet", repr(u8))]
+pub enum Rounding {
+    /// Round towards negative infinity.
+    Floor,
+    /// Round towards positive infinity.
+    Ceil,
+    /// Round towards zero.
+    TowardZero,
+    /// Round to the nearest value, ties away from zero.
+    #[default]
+    Nearest,
+}
+
+/// An error converting between a [`Tick`] and a duration type of another
+/// crate.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum DurationError {
+    /// The target type can not represent negative durations.
+    Negative,
+    /// The value does not fit into the target type.
+    Overflow,
+}
+
+impl core::fmt::Display for DurationError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Negative => write!(f, "duration can not be negative"),
+            Self::Overflow => write!(f, "duration out of range"),
+        }
+    }
+}
+
+impl core::error::Error for DurationError {}
+
+/// An error parsing or converting a [`FrameRate`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum FrameRateError {
+    /// The string is not a frame rate like `24`, `29.97` or `30000/1001`.
+    Syntax,
+    /// The frame rate is zero.
+    Zero,
+    /// The numerator or denominator does not fit a `u32`.
+    Overflow,
+    /// The floating point rate is not exactly a supported fraction.
+    Inexact,
+}
+
+impl core::fmt::Display for FrameRateError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Syntax => write!(
+                f,
+                "frame rate must be of the form `24`, `29.97` or `30000/1001`"
+            ),
+            Self::Zero => write!(f, "frame rate can not be zero"),
+            Self::Overflow => write!(f, "frame rate out of range"),
+            Self::Inexact => {
+                write!(f, "frame rate is not exactly a supported fraction")
+            }
+        }
+    }
+}
+
+impl core::error::Error for FrameRateError {}
+
+/// Nanoseconds per second.
+pub(crate) const NANOS_PER_SECOND: i128 = 1_000_000_000;
+
+/// Greatest common divisor.
+#[inline]
+pub(crate) const fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while 0 != b {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Parse a non-empty string of ASCII digits.
+pub(crate) fn parse_digits(s: &str) -> Option<u64> {
+    if s.is_empty() || !s.bytes().all(|byte| byte.is_ascii_digit()) {
+        return None;
+    }
+
+    s.parse().ok()
+}
+
+/// Divide `numer` by the positive `denom`, rounding as requested.
+#[inline]
+pub(crate) const fn div_round(
+    numer: i128,
+    denom: i128,
+    rounding: Rounding,
+) -> i128 {
+    let floor = numer.div_euclid(denom);
+    let remainder = numer.rem_euclid(denom);
+    if 0 == remainder {
+        return floor;
+    }
+
+    match rounding {
+        Rounding::Floor => floor,
+        Rounding::Ceil => floor + 1,
+        Rounding::TowardZero if numer < 0 => floor + 1,
+        Rounding::TowardZero => floor,
+        Rounding::Nearest => {
+            let rest = denom - remainder;
+            if remainder > rest || (remainder == rest && 0 <= numer) {
+                floor + 1
+            } else {
+                floor
+            }
+        }
+    }
+}
+
+/// The first tick of `frame` at `frame_rate`.
+///
+/// A tick belongs to the frame it is in, i.e. frames are found by flooring.
+/// So the first tick of a frame is found by rounding its exact start up.
+#[inline]
+pub(crate) const fn frame_start(frame: i128, frame_rate: FrameRate) -> i128 {
+    div_round(
+        frame * TICKS_PER_SECOND as i128 * frame_rate.den.get() as i128,
+        frame_rate.num.get() as i128,
+        Rounding::Ceil,
+    )
+}
+
 /// Fixed-point representation of time where each second is divided into
 /// [`TICKS_PER_SECOND`].
 ///
@@ -210,63 +907,335 @@ pub struct Tick(i64);
 
 impl IntoIterator for Tick {
     type IntoIter = TickIter;
-    type Item = i64;
+    type Item = Tick;
 
+    /// Iterate over all ticks from this one up to, but excluding,
+    /// `i64::MAX`.
     fn into_iter(self) -> Self::IntoIter {
-        TickIter(self.0)
+        TickIter::new(self..Tick(i64::MAX))
     }
 }
 
-/// An iterator over [`Tick`]s.
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+/// An iterator over a range of [`Tick`]s in ascending order.
+///
+/// Use [`TickRevIter`] or [`Iterator::rev()`] for descending order.
+///
+/// # Example
+/// ```
+/// use frame_tick::{Tick, TickIter};
+///
+/// let mut ticks = TickIter::new(Tick::new(-2)..Tick::new(2));
+/// assert_eq!(4, ticks.len());
+/// assert_eq!(Some(Tick::new(-2)), ticks.next());
+/// assert_eq!(Some(Tick::new(1)), ticks.next_back());
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "facet", derive(Facet))]
-pub struct TickIter(i64);
+pub struct TickIter {
+    /// The next tick from the front.
+    front: i64,
+    /// One past the next tick from the back.
+    back: i64,
+}
+
+impl TickIter {
+    /// Iterate over `range` in ascending order.
+    ///
+    /// The iterator is empty if the range is.
+    #[inline]
+    pub const fn new(range: Range<Tick>) -> Self {
+        Self {
+            front: range.start.0,
+            back: if range.start.0 < range.end.0 {
+                range.end.0
+            } else {
+                range.start.0
+            },
+        }
+    }
+
+    /// The ticks not yet yielded.
+    #[inline]
+    pub const fn remaining(&self) -> Range<Tick> {
+        Tick(self.front)..Tick(self.back)
+    }
+
+    #[inline]
+    fn nth_front(&mut self, n: usize) -> Option<Tick> {
+        match Tick(self.front).forward_checked(n) {
+            Some(tick) if tick.0 < self.back => {
+                self.front = tick.0 + 1;
+                Some(tick)
+            }
+            _ => {
+                self.front = self.back;
+                None
+            }
+        }
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Tick> {
+        match Tick(self.back).backward_checked(n.saturating_add(1)) {
+            Some(tick) if self.front <= tick.0 => {
+                self.back = tick.0;
+                Some(tick)
+            }
+            _ => {
+                self.back = self.front;
+                None
+            }
+        }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        Tick::steps_between(Tick(self.front), Tick(self.back))
+            .unwrap_or(usize::MAX)
+    }
+}
+
+impl From<Range<Tick>> for TickIter {
+    #[inline]
+    fn from(range: Range<Tick>) -> Self {
+        Self::new(range)
+    }
+}
 
 impl Iterator for TickIter {
-    type Item = i64;
+    type Item = Tick;
 
+    #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        if i64::MAX == self.0 {
-            None
-        } else {
-            let value = self.0;
-            self.0 += 1;
+        self.nth_front(0)
+    }
 
-            Some(value)
-        }
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.nth_front(n)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = TickIter::len(self);
+        (len, Some(len))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        TickIter::len(&self)
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
     }
 }
 
 impl DoubleEndedIterator for TickIter {
+    #[inline]
     fn next_back(&mut self) -> Option<Self::Item> {
-        if i64::MIN == self.0 {
-            None
-        } else {
-            self.0 -= 1;
-            Some(self.0)
-        }
+        TickIter::nth_back(self, 0)
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        TickIter::nth_back(self, n)
     }
 }
 
-/// An iterator over [`Tick`]s in reverse order.
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+impl ExactSizeIterator for TickIter {}
+
+impl FusedIterator for TickIter {}
+
+/// An iterator over a range of [`Tick`]s in descending order.
+///
+/// # Example
+/// ```
+/// use frame_tick::{Tick, TickRevIter};
+///
+/// let ticks = TickRevIter::new(Tick::new(0)..Tick::new(3));
+/// assert!(ticks.eq([2, 1, 0].map(Tick::new)));
+/// ```
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
 #[cfg_attr(feature = "facet", derive(Facet))]
-pub struct TickRevIter(i64);
+pub struct TickRevIter(TickIter);
+
+impl TickRevIter {
+    /// Iterate over `range` in descending order.
+    ///
+    /// The iterator is empty if the range is.
+    #[inline]
+    pub const fn new(range: Range<Tick>) -> Self {
+        Self(TickIter::new(range))
+    }
+
+    /// The ticks not yet yielded.
+    #[inline]
+    pub const fn remaining(&self) -> Range<Tick> {
+        self.0.remaining()
+    }
+}
+
+impl From<Range<Tick>> for TickRevIter {
+    #[inline]
+    fn from(range: Range<Tick>) -> Self {
+        Self::new(range)
+    }
+}
 
 impl Iterator for TickRevIter {
-    type Item = i64;
+    type Item = Tick;
 
+    #[inline]
     fn next(&mut self) -> Option<Self::Item> {
-        let value = self.0;
-        self.0 -= 1;
-        if i64::MIN == self.0 {
+        self.0.next_back()
+    }
+
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.0.nth_back(n)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.0.count()
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl DoubleEndedIterator for TickRevIter {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.0.next()
+    }
+
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.0.nth(n)
+    }
+}
+
+impl ExactSizeIterator for TickRevIter {}
+
+impl FusedIterator for TickRevIter {}
+
+/// An iterator over the frames of a [`FrameRate`] that start inside a range
+/// of [`Tick`]s.
+///
+/// Yields the frame number and the first tick of each frame. Every frame
+/// start is computed from its frame number so fractional rates like 29.97
+/// fps never accumulate drift.
+///
+/// Created by [`FrameRate::frames()`]. Use [`Iterator::rev()`] to step
+/// backwards and [`Iterator::step_by()`] to skip frames; both are `O(1)` per
+/// item.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct FrameIter {
+    frame_rate: FrameRate,
+    /// The next frame from the front.
+    front: i128,
+    /// One past the next frame from the back.
+    back: i128,
+}
+
+impl FrameIter {
+    /// The frames of `frame_rate` whose first tick lies in `range`.
+    pub fn new(range: Range<Tick>, frame_rate: FrameRate) -> Self {
+        // The first frame starting at or after a tick is the one after the
+        // frame containing the tick before it.
+        let after = |tick: Tick| {
+            div_round(
+                (tick.0 as i128 - 1) * frame_rate.num() as i128,
+                TICKS_PER_SECOND as i128 * frame_rate.den() as i128,
+                Rounding::Floor,
+            ) + 1
+        };
+        let front = after(range.start);
+
+        Self {
+            frame_rate,
+            front,
+            back: after(range.end).max(front),
+        }
+    }
+
+    #[inline]
+    fn item(&self, frame: i128) -> (i64, Tick) {
+        (frame as _, Tick(frame_start(frame, self.frame_rate) as _))
+    }
+}
+
+impl Iterator for FrameIter {
+    type Item = (i64, Tick);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.nth(0)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.front = self.front.saturating_add(n as _).min(self.back);
+        if self.front == self.back {
+            None
+        } else {
+            self.front += 1;
+            Some(self.item(self.front - 1))
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+
+    #[inline]
+    fn count(self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn last(mut self) -> Option<Self::Item> {
+        self.next_back()
+    }
+}
+
+impl DoubleEndedIterator for FrameIter {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.nth_back(0)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        self.back = self.back.saturating_sub(n as _).max(self.front);
+        if self.front == self.back {
             None
         } else {
-            Some(value)
+            self.back -= 1;
+            Some(self.item(self.back))
         }
     }
 }
 
+impl ExactSizeIterator for FrameIter {
+    #[inline]
+    fn len(&self) -> usize {
+        usize::try_from(self.back - self.front).unwrap_or(usize::MAX)
+    }
+}
+
+impl FusedIterator for FrameIter {}
+
 impl AsRef<i64> for Tick {
     fn as_ref(&self) -> &i64 {
         &self.0
@@ -358,41 +1327,128 @@ impl FromStr for Tick {
     }
 }
 
-impl Add for Tick {
+/// Implement a binary operator for all combinations of owned and borrowed
+/// operands.
+macro_rules! impl_binop {
+    (
+        $op:ident,
+        $method:ident,
+        $rhs:ty,
+        $output:ty,
+        |$lhs_:ident, $rhs_:ident| $body:expr
+    ) => {
+        impl $op<$rhs> for Tick {
+            type Output = $output;
+
+            #[inline]
+            fn $method(self, $rhs_: $rhs) -> Self::Output {
+                let $lhs_ = self;
+                $body
+            }
+        }
+
+        impl $op<&$rhs> for Tick {
+            type Output = $output;
+
+            #[inline]
+            fn $method(self, rhs: &$rhs) -> Self::Output {
+                $op::$method(self, *rhs)
+            }
+        }
+
+        impl $op<$rhs> for &Tick {
+            type Output = $output;
+
+            #[inline]
+            fn $method(self, rhs: $rhs) -> Self::Output {
+                $op::$method(*self, rhs)
+            }
+        }
+
+        impl $op<&$rhs> for &Tick {
+            type Output = $output;
+
+            #[inline]
+            fn $method(self, rhs: &$rhs) -> Self::Output {
+                $op::$method(*self, *rhs)
+            }
+        }
+    };
+}
+
+/// Implement an assigning operator for owned and borrowed operands.
+macro_rules! impl_assign_op {
+    ($op:ident, $method:ident, $binop:ident, $bin_method:ident, $rhs:ty) => {
+        impl $op<$rhs> for Tick {
+            #[inline]
+            fn $method(&mut self, rhs: $rhs) {
+                *self = $binop::$bin_method(*self, rhs);
+            }
+        }
+
+        impl $op<&$rhs> for Tick {
+            #[inline]
+            fn $method(&mut self, rhs: &$rhs) {
+                *self = $binop::$bin_method(*self, *rhs);
+            }
+        }
+    };
+}
+
+impl_binop!(Add, add, Tick, Tick, |lhs, rhs| Tick(lhs.0 + rhs.0));
+impl_binop!(Sub, sub, Tick, Tick, |lhs, rhs| Tick(lhs.0 - rhs.0));
+// Wrapping to a loop length; keeps the sign of the dividend like `%` on
+// integers. See `Tick::rem_euclid()` for a result that is never negative.
+impl_binop!(Rem, rem, Tick, Tick, |lhs, rhs| Tick(lhs.0 % rhs.0));
+// The ratio of two durations.
+impl_binop!(Div, div, Tick, f64, |lhs, rhs| lhs.0 as f64 / rhs.0 as f64);
+
+impl_assign_op!(AddAssign, add_assign, Add, add, Tick);
+impl_assign_op!(SubAssign, sub_assign, Sub, sub, Tick);
+impl_assign_op!(RemAssign, rem_assign, Rem, rem, Tick);
+
+impl Neg for Tick {
     type Output = Tick;
 
-    fn add(self, rhs: Self) -> Self::Output {
-        Tick(self.0 + rhs.0)
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Tick(-self.0)
     }
 }
 
-impl Sub for Tick {
+impl Neg for &Tick {
     type Output = Tick;
 
-    fn sub(self, rhs: Self) -> Self::Output {
-        Tick(self.0 - rhs.0)
+    #[inline]
+    fn neg(self) -> Self::Output {
+        -*self
     }
 }
 
-macro_rules! impl_mul_div_float {
-    ($ty:ty) => {
-        impl Mul<$ty> for Tick {
-            type Output = Self;
-
-            fn mul(self, rhs: $ty) -> Self::Output {
-                let value = self.0 as $ty * rhs;
-                Self(round!($ty, value))
-            }
-        }
+impl Sum for Tick {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Tick(0), Add::add)
+    }
+}
 
-        impl Div<$ty> for Tick {
-            type Output = Self;
+impl<'a> Sum<&'a Tick> for Tick {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Tick(0), Add::add)
+    }
+}
 
-            fn div(self, rhs: $ty) -> Self::Output {
-                let value = self.0 as $ty / rhs;
-                Self(round!($ty, value))
-            }
-        }
+macro_rules! impl_mul_div_float {
+    ($ty:ty) => {
+        impl_binop!(Mul, mul, $ty, Tick, |lhs, rhs| {
+            let value = lhs.0 as $ty * rhs;
+            Tick(round!($ty, value))
+        });
+        impl_binop!(Div, div, $ty, Tick, |lhs, rhs| {
+            let value = lhs.0 as $ty / rhs;
+            Tick(round!($ty, value))
+        });
+        impl_assign_op!(MulAssign, mul_assign, Mul, mul, $ty);
+        impl_assign_op!(DivAssign, div_assign, Div, div, $ty);
     };
 }
 
@@ -401,21 +1457,14 @@ impl_mul_div_float!(f64);
 
 macro_rules! impl_mul_div_int {
     ($ty:ty) => {
-        impl Mul<$ty> for Tick {
-            type Output = Self;
-
-            fn mul(self, rhs: $ty) -> Self::Output {
-                Self((self.0 as $ty * rhs) as _)
-            }
-        }
-
-        impl Div<$ty> for Tick {
-            type Output = Self;
-
-            fn div(self, rhs: $ty) -> Self::Output {
-                Self((self.0 as $ty / rhs) as _)
-            }
-        }
+        impl_binop!(Mul, mul, $ty, Tick, |lhs, rhs| Tick(
+            (lhs.0 as $ty * rhs) as _
+        ));
+        impl_binop!(Div, div, $ty, Tick, |lhs, rhs| Tick(
+            (lhs.0 as $ty / rhs) as _
+        ));
+        impl_assign_op!(MulAssign, mul_assign, Mul, mul, $ty);
+        impl_assign_op!(DivAssign, div_assign, Div, div, $ty);
     };
 }
 
@@ -434,22 +1483,167 @@ impl_mul_div_int!(isize);
 
 impl Tick {
     #[inline]
-    pub fn new(value: i64) -> Self {
+    pub const fn new(value: i64) -> Self {
         Self(value)
     }
 
+    /// The absolute value.
+    #[inline]
+    pub const fn abs(self) -> Self {
+        Self(self.0.abs())
+    }
+
+    /// `-1`, `0` or `1` depending on the sign.
+    #[inline]
+    pub const fn signum(self) -> i64 {
+        self.0.signum()
+    }
+
+    /// How many whole `rhs` fit into this, rounded towards negative
+    /// infinity for positive `rhs`.
+    ///
+    /// Use this to find the loop iteration or frame a (possibly negative)
+    /// tick falls into.
+    #[inline]
+    pub const fn div_euclid(self, rhs: Self) -> i64 {
+        self.0.div_euclid(rhs.0)
+    }
+
+    /// The remainder of [`div_euclid()`](Self::div_euclid), which is never
+    /// negative.
+    ///
+    /// Use this to wrap a (possibly negative) tick to a loop length.
+    #[inline]
+    pub const fn rem_euclid(self, rhs: Self) -> Self {
+        Self(self.0.rem_euclid(rhs.0))
+    }
+
+    /// The tick `count` ticks after this one, or `None` on overflow.
+    ///
+    /// Mirrors the unstable [`core::iter::Step::forward_checked()`].
+    #[inline]
+    pub const fn forward_checked(self, count: usize) -> Option<Self> {
+        match self.0.checked_add_unsigned(count as _) {
+            Some(value) => Some(Self(value)),
+            None => None,
+        }
+    }
+
+    /// The tick `count` ticks before this one, or `None` on overflow.
+    ///
+    /// Mirrors the unstable [`core::iter::Step::backward_checked()`].
+    #[inline]
+    pub const fn backward_checked(self, count: usize) -> Option<Self> {
+        match self.0.checked_sub_unsigned(count as _) {
+            Some(value) => Some(Self(value)),
+            None => None,
+        }
+    }
+
+    /// The number of ticks from `start` up to `end`.
+    ///
+    /// Returns `None` if `end` is before `start` or the number does not fit
+    /// a `usize`. Mirrors the unstable [`core::iter::Step::steps_between()`].
+    #[inline]
+    pub const fn steps_between(start: Self, end: Self) -> Option<usize> {
+        if end.0 < start.0 {
+            return None;
+        }
+
+        let steps = end.0.abs_diff(start.0);
+        if steps <= usize::MAX as u64 {
+            Some(steps as _)
+        } else {
+            None
+        }
+    }
+
     /// Create ticks from seconds.
     #[inline]
     pub fn from_secs(secs: f64) -> Self {
         Self((secs * TICKS_PER_SECOND as f64) as i64)
     }
 
+    /// Create ticks from whole seconds.
+    #[inline]
+    pub const fn from_secs_int(secs: i64) -> Self {
+        Self(secs * TICKS_PER_SECOND)
+    }
+
+    /// Create ticks from a frame number at an integer frame rate.
+    ///
+    /// This is the `const` version of [`FrameRateConversion::from_frames()`].
+    ///
+    /// # Example
+    /// ```
+    /// use frame_tick::{FramesPerSec, Tick};
+    ///
+    /// const TWO_SECONDS: Tick =
+    ///     Tick::from_frames_at_fps(48, FramesPerSec::new(24).unwrap());
+    /// assert_eq!(Tick::from_secs_int(2), TWO_SECONDS);
+    /// ```
+    #[inline]
+    pub const fn from_frames_at_fps(
+        frames: i64,
+        frame_rate: FramesPerSec,
+    ) -> Self {
+        Self(
+            (frames as i128 * TICKS_PER_SECOND as i128
+                / frame_rate.get() as i128) as _,
+        )
+    }
+
+    /// The first tick of frame number `frames` at `frame_rate`.
+    ///
+    /// If the frame rate is not [exact](FrameRate::is_exact) the frame start
+    /// is rounded up to the next tick, so the tick lies inside the frame.
+    #[inline]
+    pub const fn from_frames_at_rate(
+        frames: i64,
+        frame_rate: FrameRate,
+    ) -> Self {
+        Self(frame_start(frames as _, frame_rate) as _)
+    }
+
     /// Convert ticks to seconds.
     #[inline]
     pub fn to_secs(&self) -> f64 {
         self.0 as f64 / TICKS_PER_SECOND as f64
     }
 
+    /// Create ticks from nanoseconds.
+    ///
+    /// A nanosecond is not an integral number of ticks so this rounds as
+    /// requested, see [`to_nanos()`](Self::to_nanos).
+    #[inline]
+    pub fn from_nanos(
+        nanos: i128,
+        rounding: Rounding,
+    ) -> Result<Self, DurationError> {
+        nanos
+            .checked_mul(TICKS_PER_SECOND as _)
+            .map(|ticks| div_round(ticks, NANOS_PER_SECOND, rounding))
+            .and_then(|ticks| i64::try_from(ticks).ok())
+            .map(Self)
+            .ok_or(DurationError::Overflow)
+    }
+
+    /// Convert ticks to nanoseconds.
+    ///
+    /// Neither a tick nor a nanosecond is a multiple of the other, so this
+    /// rounds as requested. The conversions to and from the duration types
+    /// of `chrono`, `time` and `jiff` go through an integer number of
+    /// nanoseconds with [`Rounding::Nearest`], i.e. ties away from zero. Use
+    /// this and [`from_nanos()`](Self::from_nanos) for other rounding modes.
+    #[inline]
+    pub fn to_nanos(self, rounding: Rounding) -> i128 {
+        div_round(
+            self.0 as i128 * NANOS_PER_SECOND,
+            TICKS_PER_SECOND as _,
+            rounding,
+        )
+    }
+
     /// Linearly interpolate between two ticks.
     #[inline]
     pub fn lerp(self, other: Self, t: f64) -> Self {
@@ -462,7 +1656,10 @@ impl Tick {
     ///
     /// Returns `(hours, minutes, seconds, frames)`.
     #[inline]
-    pub fn to_timecode(self, frame_rate: FrameRate) -> (i64, i64, i64, i64) {
+    pub const fn to_timecode(
+        self,
+        frame_rate: FrameRate,
+    ) -> (i64, i64, i64, i64) {
         // Calculate total frames using exact frame rate (with rounding):
         // total_frames = ticks * (num/den) / TICKS_PER_SECOND
         let divisor = TICKS_PER_SECOND as i128 * frame_rate.den() as i128;
@@ -488,7 +1685,7 @@ impl Tick {
     /// Create ticks from timecode (hours, minutes, seconds, frames) at the
     /// given frame rate.
     #[inline]
-    pub fn from_timecode(
+    pub const fn from_timecode(
         hours: i64,
         minutes: i64,
         seconds: i64,
@@ -532,10 +1729,7 @@ impl FrameRateConversion<FramesPerSec> for Tick {
 
     /// Convert frame number to ticks at the specified integer frame rate.
     fn from_frames(frames: i64, frame_rate: FramesPerSec) -> Self {
-        Self(
-            (frames as i128 * TICKS_PER_SECOND as i128
-                / frame_rate.get() as i128) as _,
-        )
+        Self::from_frames_at_fps(frames, frame_rate)
     }
 }
 
@@ -0,0 +1,442 @@
+//! Interop with [OpenTimelineIO](https://opentimeline.io/)'s `RationalTime`
+//! and `TimeRange`.
+//!
+//! OTIO stores time as a pair of `f64`s: a `value` counted at some `rate`. A
+//! conversion between such a pair and a [`Tick`] is only lossless if the
+//! `value` denotes an integral number of ticks. Everything in this module
+//! checks this exactly and returns [`OtioError::Inexact`] instead of silently
+//! rounding.
+//!
+//! The [`serde`](https://docs.rs/serde/) implementations read and write the
+//! JSON representation used in `.otio` files, including the `OTIO_SCHEMA`
+//! tag.
+//!
+//! # Examples
+//!
+//! ```
+//! use frame_tick::{FrameRate, Tick, otio::RationalTime};
+//!
+//! let tick = Tick::from_secs(2.0);
+//! let time = RationalTime::from_tick(tick, FrameRate::FILM).unwrap();
+//!
+//! assert_eq!(48.0, time.value);
+//! assert_eq!(24.0, time.rate);
+//! assert_eq!(tick, Tick::try_from(time).unwrap());
+//! ```
+use crate::{FrameRate, Rounding, TICKS_PER_SECOND, Tick, div_round};
+use core::{
+    fmt::{self, Display, Formatter},
+    ops::Range,
+};
+use serde::{
+    Deserialize, Deserializer, Serialize, Serializer,
+    de::{self, DeserializeSeed, IgnoredAny, MapAccess, Unexpected, Visitor},
+    ser::SerializeStruct,
+};
+
+const RATIONAL_TIME_SCHEMA: &str = "RationalTime.1";
+const TIME_RANGE_SCHEMA: &str = "TimeRange.1";
+
+/// An error converting between OTIO time and [`Tick`]s.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum OtioError {
+    /// The `value` or `rate` is `NaN` or infinite.
+    NonFinite,
+    /// The `rate` is not a [`FrameRate`], see [`FrameRate::from_f64()`].
+    InvalidRate(f64),
+    /// The conversion would have to round. Carries the nearest [`Tick`].
+    Inexact {
+        /// The nearest tick to the requested time.
+        nearest: Tick,
+    },
+    /// The result does not fit into a [`Tick`].
+    Overflow,
+}
+
+impl Display for OtioError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NonFinite => write!(f, "OTIO time is not finite"),
+            Self::InvalidRate(rate) => {
+                write!(f, "OTIO rate {rate} has no exact frame rate")
+            }
+            Self::Inexact { nearest } => write!(
+                f,
+                "OTIO time is not an integral number of ticks (nearest: {})",
+                nearest.0
+            ),
+            Self::Overflow => write!(f, "OTIO time overflows a tick"),
+        }
+    }
+}
+
+impl core::error::Error for OtioError {}
+
+/// A point in time, mirroring OTIO's `RationalTime`.
+///
+/// The time in seconds is `value / rate`.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct RationalTime {
+    /// The time, counted in units of `1 / rate` seconds.
+    pub value: f64,
+    /// The number of units per second.
+    pub rate: f64,
+}
+
+impl RationalTime {
+    /// Create a new rational time.
+    #[inline]
+    pub fn new(value: f64, rate: f64) -> Self {
+        Self { value, rate }
+    }
+
+    /// Express `tick` as a number of frames at `frame_rate`.
+    ///
+    /// The `value` may be fractional if `tick` does not fall onto the frame
+    /// grid. Returns [`OtioError::Inexact`] if the resulting `f64`s do not
+    /// convert back to the same `tick`.
+    pub fn from_tick(
+        tick: Tick,
+        frame_rate: FrameRate,
+    ) -> Result<Self, OtioError> {
+        // value = tick * num / (TICKS_PER_SECOND * den)
+        let numer = tick.0 as i128 * frame_rate.num() as i128;
+        let denom = TICKS_PER_SECOND as i128 * frame_rate.den() as i128;
+        let value = if 0 == numer % denom {
+            (numer / denom) as f64
+        } else {
+            numer as f64 / denom as f64
+        };
+
+        let time = Self::new(value, frame_rate.to_f64());
+        match time.to_tick_at(frame_rate) {
+            Ok(round_trip) if round_trip == tick => Ok(time),
+            Ok(nearest) => Err(OtioError::Inexact { nearest }),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Express `tick` at a rate of [`TICKS_PER_SECOND`].
+    ///
+    /// This is exact for all ticks whose magnitude is below 2⁵³.
+    pub fn from_tick_at_tick_rate(tick: Tick) -> Result<Self, OtioError> {
+        Self::from_tick(
+            tick,
+            FrameRate::from_int(TICKS_PER_SECOND as _).unwrap(),
+        )
+    }
+
+    /// The exact [`FrameRate`] this time is counted at.
+    ///
+    /// OTIO stores rates like 29.97 as the `f64` closest to `30000/1001`.
+    /// This recovers the fraction via [`FrameRate::from_f64()`].
+    pub fn frame_rate(self) -> Result<FrameRate, OtioError> {
+        if !self.rate.is_finite() {
+            return Err(OtioError::NonFinite);
+        }
+
+        FrameRate::from_f64(self.rate).ok_or(OtioError::InvalidRate(self.rate))
+    }
+
+    /// Convert to a [`Tick`], failing if this is not an integral number of
+    /// ticks.
+    pub fn to_tick(self) -> Result<Tick, OtioError> {
+        self.to_tick_at(self.frame_rate()?)
+    }
+
+    fn to_tick_at(self, frame_rate: FrameRate) -> Result<Tick, OtioError> {
+        if !self.value.is_finite() {
+            return Err(OtioError::NonFinite);
+        }
+        if 0.0 == self.value {
+            return Ok(Tick(0));
+        }
+
+        // value == mantissa * 2^exponent, exactly.
+        let (mut mantissa, mut exponent) = decompose(self.value);
+        while 0 == mantissa & 1 && exponent < 0 {
+            mantissa >>= 1;
+            exponent += 1;
+        }
+
+        // ticks = value * TICKS_PER_SECOND * den / num
+        let mut numer = mantissa as i128
+            * TICKS_PER_SECOND as i128
+            * frame_rate.den() as i128;
+        let mut denom = frame_rate.num() as i128;
+        if 0 <= exponent {
+            numer = (exponent < 127)
+                .then(|| numer.checked_mul(1 << exponent))
+                .flatten()
+                .ok_or(OtioError::Overflow)?;
+        } else if -exponent < 64 {
+            denom <<= -exponent;
+        } else {
+            // An odd mantissa over a power of two this large can never be
+            // cancelled out by the ticks per second and the denominator.
+            return Err(OtioError::Inexact {
+                nearest: Tick::from(
+                    self.value
+                        * TICKS_PER_SECOND as f64
+                        * frame_rate.den() as f64
+                        / frame_rate.num() as f64,
+                ),
+            });
+        }
+
+        if 0 == numer % denom {
+            i64::try_from(numer / denom)
+                .map(Tick)
+                .map_err(|_| OtioError::Overflow)
+        } else {
+            Err(OtioError::Inexact {
+                nearest: Tick(div_round(numer, denom, Rounding::Nearest) as _),
+            })
+        }
+    }
+}
+
+impl TryFrom<RationalTime> for Tick {
+    type Error = OtioError;
+
+    fn try_from(time: RationalTime) -> Result<Self, Self::Error> {
+        time.to_tick()
+    }
+}
+
+/// A time range, mirroring OTIO's `TimeRange`.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct TimeRange {
+    /// The start of the range.
+    pub start_time: RationalTime,
+    /// The length of the range.
+    pub duration: RationalTime,
+}
+
+impl TimeRange {
+    /// Create a new time range.
+    #[inline]
+    pub fn new(start_time: RationalTime, duration: RationalTime) -> Self {
+        Self {
+            start_time,
+            duration,
+        }
+    }
+
+    /// Express a tick range as a time range counted at `frame_rate`.
+    pub fn from_range(
+        range: Range<Tick>,
+        frame_rate: FrameRate,
+    ) -> Result<Self, OtioError> {
+        let duration = range
+            .end
+            .0
+            .checked_sub(range.start.0)
+            .ok_or(OtioError::Overflow)?;
+
+        Ok(Self::new(
+            RationalTime::from_tick(range.start, frame_rate)?,
+            RationalTime::from_tick(Tick(duration), frame_rate)?,
+        ))
+    }
+
+    /// Convert to a tick range, failing if either end is not an integral
+    /// number of ticks.
+    pub fn to_range(self) -> Result<Range<Tick>, OtioError> {
+        let start = self.start_time.to_tick()?;
+        let duration = self.duration.to_tick()?;
+
+        Ok(start
+            ..Tick(start.0.checked_add(duration.0).ok_or(OtioError::Overflow)?))
+    }
+}
+
+impl TryFrom<TimeRange> for Range<Tick> {
+    type Error = OtioError;
+
+    fn try_from(range: TimeRange) -> Result<Self, Self::Error> {
+        range.to_range()
+    }
+}
+
+/// Split a finite, non-zero `f64` into `(mantissa, exponent)` such that
+/// `value == mantissa * 2^exponent`.
+fn decompose(value: f64) -> (i64, i32) {
+    let bits = value.to_bits();
+    let sign = if 0 == bits >> 63 { 1 } else { -1 };
+    let exponent = ((bits >> 52) & 0x7ff) as i32;
+    let fraction = bits & 0xf_ffff_ffff_ffff;
+    let mantissa = if 0 == exponent {
+        fraction << 1
+    } else {
+        fraction | 0x10_0000_0000_0000
+    };
+
+    (sign * mantissa as i64, exponent - 1075)
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "snake_case")]
+enum Field {
+    #[serde(rename = "OTIO_SCHEMA")]
+    Schema,
+    Rate,
+    Value,
+    StartTime,
+    Duration,
+    #[serde(other)]
+    Other,
+}
+
+/// Checks the `OTIO_SCHEMA` tag without allocating.
+struct Schema(&'static str);
+
+impl<'de> DeserializeSeed<'de> for Schema {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl Visitor<'_> for Schema {
+    type Value = ();
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "the schema `{}`", self.0)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<(), E> {
+        if value == self.0 {
+            Ok(())
+        } else {
+            Err(E::invalid_value(Unexpected::Str(value), &self))
+        }
+    }
+}
+
+impl Serialize for RationalTime {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("RationalTime", 3)?;
+        state.serialize_field("OTIO_SCHEMA", RATIONAL_TIME_SCHEMA)?;
+        state.serialize_field("rate", &self.rate)?;
+        state.serialize_field("value", &self.value)?;
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for RationalTime {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        struct RationalTimeVisitor;
+
+        impl<'de> Visitor<'de> for RationalTimeVisitor {
+            type Value = RationalTime;
+
+            fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                write!(f, "an OTIO `{RATIONAL_TIME_SCHEMA}`")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<Self::Value, A::Error> {
+                let (mut rate, mut value) = (None, None);
+                while let Some(field) = map.next_key()? {
+                    match field {
+                        Field::Schema => {
+                            map.next_value_seed(Schema(RATIONAL_TIME_SCHEMA))?
+                        }
+                        Field::Rate => rate = Some(map.next_value()?),
+                        Field::Value => value = Some(map.next_value()?),
+                        _ => {
+                            map.next_value::<IgnoredAny>()?;
+                        }
+                    }
+                }
+
+                Ok(RationalTime::new(
+                    value.ok_or_else(|| de::Error::missing_field("value"))?,
+                    rate.ok_or_else(|| de::Error::missing_field("rate"))?,
+                ))
+            }
+        }
+
+        deserializer.deserialize_struct(
+            "RationalTime",
+            &["OTIO_SCHEMA", "rate", "value"],
+            RationalTimeVisitor,
+        )
+    }
+}
+
+impl Serialize for TimeRange {
+    fn serialize<S: Serializer>(
+        &self,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("TimeRange", 3)?;
+        state.serialize_field("OTIO_SCHEMA", TIME_RANGE_SCHEMA)?;
+        state.serialize_field("duration", &self.duration)?;
+        state.serialize_field("start_time", &self.start_time)?;
+        state.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for TimeRange {
+    fn deserialize<D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        struct TimeRangeVisitor;
+
+        impl<'de> Visitor<'de> for TimeRangeVisitor {
+            type Value = TimeRange;
+
+            fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                write!(f, "an OTIO `{TIME_RANGE_SCHEMA}`")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<Self::Value, A::Error> {
+                let (mut start_time, mut duration) = (None, None);
+                while let Some(field) = map.next_key()? {
+                    match field {
+                        Field::Schema => {
+                            map.next_value_seed(Schema(TIME_RANGE_SCHEMA))?
+                        }
+                        Field::StartTime => {
+                            start_time = Some(map.next_value()?)
+                        }
+                        Field::Duration => duration = Some(map.next_value()?),
+                        _ => {
+                            map.next_value::<IgnoredAny>()?;
+                        }
+                    }
+                }
+
+                Ok(TimeRange::new(
+                    start_time.ok_or_else(|| {
+                        de::Error::missing_field("start_time")
+                    })?,
+                    duration
+                        .ok_or_else(|| de::Error::missing_field("duration"))?,
+                ))
+            }
+        }
+
+        deserializer.deserialize_struct(
+            "TimeRange",
+            &["OTIO_SCHEMA", "duration", "start_time"],
+            TimeRangeVisitor,
+        )
+    }
+}
@@ -0,0 +1,180 @@
+//! Snap ticks to frame, step or beat grids.
+//!
+//! A [`Grid`] is a sequence of equally spaced points, starting at an origin.
+//! The spacing need not be an integral number of ticks, e.g. for frames at
+//! 29.97 fps. Like frames, each point is then the first tick at or after
+//! its exact position, so snapping to a frame grid gives the same ticks as
+//! [`frame_start()`](crate::frame::frame_start).
+//!
+//! # Examples
+//!
+//! ```
+//! use core::num::NonZeroU32;
+//! use frame_tick::{
+//!     FrameRate, FrameRateConversion, Rounding, Tick, quantize::Grid,
+//! };
+//!
+//! let tick = Tick::from_secs(1.01);
+//! let frames = Grid::frames(FrameRate::FILM);
+//! let frame = |frame| Tick::from_frames(frame, FrameRate::FILM);
+//!
+//! assert_eq!(frame(24), tick.quantize(frames, Rounding::Floor));
+//! assert_eq!(frame(24), tick.quantize(frames, Rounding::Nearest));
+//! assert_eq!(frame(25), tick.quantize(frames, Rounding::Ceil));
+//!
+//! // Every 12th frame, counted from 0.25 seconds.
+//! let grid = frames
+//!     .every(NonZeroU32::new(12).unwrap())
+//!     .with_origin(Tick::from_secs(0.25));
+//! assert_eq!(Tick::from_secs(0.75), tick.quantize(grid, Rounding::Floor));
+//! ```
+use crate::{FrameRate, Rounding, TICKS_PER_SECOND, Tick, div_round};
+use core::num::NonZeroU32;
+
+/// Equally spaced points in time, see the [module documentation](self).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Grid {
+    origin: Tick,
+    /// The spacing is `numer / denom` ticks.
+    numer: i128,
+    denom: i128,
+}
+
+impl Grid {
+    /// The frames of `frame_rate`.
+    #[inline]
+    pub fn frames(frame_rate: FrameRate) -> Self {
+        Self {
+            origin: Tick(0),
+            numer: TICKS_PER_SECOND as i128 * frame_rate.den() as i128,
+            denom: frame_rate.num() as _,
+        }
+    }
+
+    /// Points `numer / denom` ticks apart; both must be positive.
+    #[inline]
+    pub(crate) fn from_spacing(numer: i128, denom: i128) -> Self {
+        Self {
+            origin: Tick(0),
+            numer,
+            denom,
+        }
+    }
+
+    /// Points `step` apart.
+    ///
+    /// Returns `None` if `step` is not positive.
+    #[inline]
+    pub fn step(step: Tick) -> Option<Self> {
+        (0 < step.0).then_some(Self {
+            origin: Tick(0),
+            numer: step.0 as _,
+            denom: 1,
+        })
+    }
+
+    /// The beats at `beats_per_minute`.
+    #[inline]
+    pub fn beats(beats_per_minute: NonZeroU32) -> Self {
+        Self {
+            origin: Tick(0),
+            numer: 60 * TICKS_PER_SECOND as i128,
+            denom: beats_per_minute.get() as _,
+        }
+    }
+
+    /// Only every `n`th point of this grid, e.g. every 4th frame.
+    #[inline]
+    pub fn every(self, n: NonZeroU32) -> Self {
+        Self {
+            numer: self.numer * n.get() as i128,
+            ..self
+        }
+    }
+
+    /// This grid shifted to start at `origin`.
+    #[inline]
+    pub fn with_origin(self, origin: Tick) -> Self {
+        Self { origin, ..self }
+    }
+
+    /// The origin, i.e. point zero.
+    #[inline]
+    pub fn origin(self) -> Tick {
+        self.origin
+    }
+
+    /// Point number `index`, counted from the origin.
+    ///
+    /// If the point is not on an integral tick this is the tick after it.
+    #[inline]
+    pub fn point(self, index: i64) -> Tick {
+        let offset = div_round(
+            (index as i128).saturating_mul(self.numer),
+            self.denom,
+            Rounding::Ceil,
+        );
+
+        Tick(
+            (self.origin.0 as i128 + offset).clamp(i64::MIN as _, i64::MAX as _)
+                as _,
+        )
+    }
+
+    /// The number of the point `tick` snaps to with `rounding`.
+    ///
+    /// [`Rounding::Floor`] gives the point at or before `tick`,
+    /// [`Rounding::Ceil`] the point at or after it. Ties of
+    /// [`Rounding::Nearest`] and [`Rounding::TowardZero`] are decided
+    /// relative to the origin.
+    ///
+    /// Saturates at the range of `i64`, which points less than a tick apart
+    /// can exceed.
+    pub fn index(self, tick: Tick, rounding: Rounding) -> i64 {
+        let offset = tick.0 as i128 - self.origin.0 as i128;
+        // The last point at or before `tick`: `ceil(k * numer / denom) <=
+        // offset` holds iff `k * numer / denom <= offset`.
+        let floor = div_round(offset * self.denom, self.numer, Rounding::Floor);
+        let ceil = if self.point_offset(floor) == offset {
+            floor
+        } else {
+            floor + 1
+        };
+
+        let index = match rounding {
+            Rounding::Floor => floor,
+            Rounding::Ceil => ceil,
+            Rounding::TowardZero if offset < 0 => ceil,
+            Rounding::TowardZero => floor,
+            Rounding::Nearest => {
+                let below = offset - self.point_offset(floor);
+                let above = self.point_offset(ceil) - offset;
+                if below < above || below == above && offset < 0 {
+                    floor
+                } else {
+                    ceil
+                }
+            }
+        };
+
+        i64::try_from(index).unwrap_or(if index < 0 {
+            i64::MIN
+        } else {
+            i64::MAX
+        })
+    }
+
+    /// The offset of point `index` from the origin, in ticks.
+    #[inline]
+    fn point_offset(self, index: i128) -> i128 {
+        div_round(index * self.numer, self.denom, Rounding::Ceil)
+    }
+}
+
+impl Tick {
+    /// Snap this tick to a point of `grid`, see [`Grid::index()`].
+    #[inline]
+    pub fn quantize(self, grid: Grid, rounding: Rounding) -> Self {
+        grid.point(grid.index(self, rounding))
+    }
+}
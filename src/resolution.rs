@@ -0,0 +1,131 @@
+//! Find the tick resolution a set of frame and sample rates needs.
+//!
+//! A rate is covered by a tick resolution if every frame or sample starts on
+//! an integral tick. The smallest resolution covering a set of rates is the
+//! least common multiple of their per-second counts, e.g. 3,603,600 for the
+//! rates listed in the [crate documentation](crate).
+//!
+//! Everything here is `const`, so coverage can be asserted at build time.
+//!
+//! # Examples
+//!
+//! ```
+//! use frame_tick::{FrameRate, resolution::TickResolution};
+//!
+//! const DEVICES: TickResolution = match TickResolution::for_rates(
+//!     &[FrameRate::FILM, FrameRate::NTSC, FrameRate::FPS_120],
+//!     &[48_000],
+//! ) {
+//!     Ok(resolution) => resolution,
+//!     Err(_) => panic!("tick resolution overflows"),
+//! };
+//!
+//! assert_eq!(240_000, DEVICES.ticks_per_second());
+//! // 48 kHz audio is not covered by the current resolution.
+//! assert!(!DEVICES.is_covered());
+//! ```
+use crate::{FrameRate, TICKS_PER_SECOND, gcd};
+use core::fmt::{self, Display, Formatter};
+
+/// Seconds in a Julian year of 365.25 days.
+const SECONDS_PER_YEAR: u64 = 31_557_600;
+
+/// An error finding a [`TickResolution`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ResolutionError {
+    /// A sample rate is zero.
+    ZeroSampleRate,
+    /// The required resolution does not fit a `u64`.
+    Overflow,
+}
+
+impl Display for ResolutionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ZeroSampleRate => write!(f, "sample rate can not be zero"),
+            Self::Overflow => write!(f, "tick resolution overflows"),
+        }
+    }
+}
+
+impl core::error::Error for ResolutionError {}
+
+/// A number of ticks per second.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct TickResolution {
+    ticks_per_second: u64,
+}
+
+impl TickResolution {
+    /// The resolution of [`Tick`](crate::Tick), i.e. [`TICKS_PER_SECOND`].
+    pub const CURRENT: Self = Self {
+        ticks_per_second: TICKS_PER_SECOND as _,
+    };
+
+    /// The smallest resolution covering all `frame_rates` and
+    /// `sample_rates`, the latter in Hz.
+    pub const fn for_rates(
+        frame_rates: &[FrameRate],
+        sample_rates: &[u32],
+    ) -> Result<Self, ResolutionError> {
+        let mut ticks_per_second = 1;
+
+        let mut index = 0;
+        while index < frame_rates.len() {
+            let frame_rate = frame_rates[index];
+            // Frame `n` starts at `n * den / num` seconds.
+            let frames = frame_rate.num() as u64
+                / gcd(frame_rate.num() as _, frame_rate.den() as _);
+            ticks_per_second = match lcm(ticks_per_second, frames) {
+                Some(lcm) => lcm,
+                None => return Err(ResolutionError::Overflow),
+            };
+            index += 1;
+        }
+
+        let mut index = 0;
+        while index < sample_rates.len() {
+            if 0 == sample_rates[index] {
+                return Err(ResolutionError::ZeroSampleRate);
+            }
+            ticks_per_second =
+                match lcm(ticks_per_second, sample_rates[index] as _) {
+                    Some(lcm) => lcm,
+                    None => return Err(ResolutionError::Overflow),
+                };
+            index += 1;
+        }
+
+        Ok(Self { ticks_per_second })
+    }
+
+    /// The number of ticks per second.
+    #[inline]
+    pub const fn ticks_per_second(self) -> u64 {
+        self.ticks_per_second
+    }
+
+    /// How many years an `i64` tick count can represent at this resolution
+    /// in either direction, in Julian years of 365.25 days.
+    #[inline]
+    pub const fn headroom_years(self) -> u64 {
+        i64::MAX as u64 / self.ticks_per_second / SECONDS_PER_YEAR
+    }
+
+    /// Whether every rate this resolution covers is also covered by
+    /// `other`.
+    #[inline]
+    pub const fn is_covered_by(self, other: Self) -> bool {
+        other.ticks_per_second.is_multiple_of(self.ticks_per_second)
+    }
+
+    /// Whether this is covered by [`TICKS_PER_SECOND`].
+    #[inline]
+    pub const fn is_covered(self) -> bool {
+        self.is_covered_by(Self::CURRENT)
+    }
+}
+
+const fn lcm(a: u64, b: u64) -> Option<u64> {
+    (a / gcd(a, b)).checked_mul(b)
+}
@@ -0,0 +1,457 @@
+//! Ruler marks and pixel mapping for timeline widgets.
+//!
+//! A [`Viewport`] maps the visible ticks onto a row of pixels and handles
+//! zooming and panning. A [`Ruler`] picks "nice" intervals for the zoom
+//! level, i.e. the smallest interval from a fixed list whose marks are at
+//! least a minimum number of pixels apart, and yields the [`Mark`]s inside
+//! a viewport.
+//!
+//! Marks are points of a [`Grid`], so they land on the same ticks as frame
+//! starts.
+//!
+//! # Examples
+//!
+//! ```
+//! use frame_tick::{
+//!     FrameRate, Tick,
+//!     ruler::{Ruler, Scale, Viewport},
+//! };
+//!
+//! // Ten seconds on 800 pixels.
+//! let mut viewport =
+//!     Viewport::new(Tick::new(0)..Tick::from_secs_int(10), 800.0).unwrap();
+//! let ruler =
+//!     Ruler::new(Scale::Timecode { drop_frame: false }, FrameRate::FILM);
+//!
+//! let labels = ruler
+//!     .marks(&viewport)
+//!     .filter_map(|mark| Some(mark.label?.to_string()))
+//!     .collect::<Vec<_>>();
+//! assert_eq!("00:00:00:00", labels[0]);
+//! assert_eq!("00:00:01:00", labels[1]);
+//!
+//! // Zoom in around the cursor; the tick under it stays put.
+//! let cursor = viewport.to_tick(200.0);
+//! viewport.zoom(4.0, 200.0);
+//! assert_eq!(200.0, viewport.to_pixel(cursor));
+//! ```
+use crate::{
+    FrameRate, Rounding, TICKS_PER_SECOND, Tick,
+    frame::frame_containing,
+    quantize::Grid,
+    timecode::{Timecode, nominal_fps},
+};
+use core::{
+    fmt::{self, Display, Formatter},
+    iter::FusedIterator,
+    num::NonZeroU32,
+    ops::Range,
+};
+
+/// Seconds of the steps of clock-based scales, from one second to half a
+/// day. Longer steps are 1, 2 or 5 times a power of ten days.
+const CLOCK_STEPS: [u64; 17] = [
+    1, 2, 5, 10, 15, 30, 60, 120, 300, 600, 900, 1800, 3600, 7200, 10800,
+    21600, 43200,
+];
+const SECONDS_PER_DAY: u64 = 86_400;
+/// Beats are subdivided into up to this many parts.
+const BEAT_PARTS: u64 = 16;
+
+/// 1, 2 and 5 times powers of ten.
+fn decades() -> impl Iterator<Item = u64> {
+    (0..)
+        .map_while(|exponent| 10u64.checked_pow(exponent))
+        .flat_map(|power| [1, 2, 5].map(|m| power.checked_mul(m)))
+        .map_while(|step| step)
+}
+
+/// Returns `true` if `step` is 1, 2 or 5 times a power of ten.
+fn is_decade(mut step: u64) -> bool {
+    while 10 <= step && step.is_multiple_of(10) {
+        step /= 10;
+    }
+    matches!(step, 1 | 2 | 5)
+}
+
+/// Steps counted in units of `1 / per_second` seconds: decades, halves and
+/// quarters that divide a second, then [`CLOCK_STEPS`] and days.
+fn clock_steps(per_second: u64) -> impl Iterator<Item = u64> {
+    // The divisors in ascending order, found in pairs up to the square root.
+    let root = per_second.isqrt();
+    let divides = move |divisor: &u64| per_second.is_multiple_of(*divisor);
+    let divisors = (1..=root).filter(divides).chain(
+        (1..=root)
+            .rev()
+            .filter(divides)
+            .filter(move |&divisor| divisor != per_second / divisor)
+            .map(move |divisor| per_second / divisor),
+    );
+    let sub_second = divisors.filter(move |&step| {
+        step < per_second
+            && (is_decade(step) || [2, 4].contains(&(per_second / step)))
+    });
+    let seconds = CLOCK_STEPS
+        .into_iter()
+        .map(Some)
+        .chain(decades().map(|days| days.checked_mul(SECONDS_PER_DAY)));
+    sub_second.chain(
+        seconds.map_while(move |seconds| seconds?.checked_mul(per_second)),
+    )
+}
+
+/// The place-value system of a [`Ruler`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Scale {
+    /// Frame numbers, at intervals of 1, 2 or 5 times a power of ten frames.
+    Frames,
+    /// Seconds, at decimal intervals below a second and clock intervals,
+    /// e.g. 15 seconds or 5 minutes, above.
+    Seconds,
+    /// Timecodes, at intervals of a divisor of the nominal frame rate or
+    /// clock intervals.
+    ///
+    /// Marks are evenly spaced, so with drop-frame counting the labels of
+    /// marks a minute or more apart are off round values by the dropped
+    /// frames.
+    Timecode {
+        /// Whether to use drop-frame counting.
+        drop_frame: bool,
+    },
+    /// Beat numbers at the given beats per minute, at intervals of powers of
+    /// two beats. Minor marks go down to sixteenth beats.
+    Beats(NonZeroU32),
+}
+
+/// A ruler for a [`Viewport`], see the [module documentation](self).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Ruler {
+    scale: Scale,
+    frame_rate: FrameRate,
+    major_spacing: f64,
+    minor_spacing: f64,
+}
+
+impl Ruler {
+    /// A ruler with major marks at least 80 and minor marks at least 8
+    /// pixels apart.
+    ///
+    /// `frame_rate` is used for frame numbers and timecodes.
+    #[inline]
+    pub const fn new(scale: Scale, frame_rate: FrameRate) -> Self {
+        Self {
+            scale,
+            frame_rate,
+            major_spacing: 80.0,
+            minor_spacing: 8.0,
+        }
+    }
+
+    /// This ruler with major and minor marks at least `major` and `minor`
+    /// pixels apart.
+    #[inline]
+    pub const fn with_spacing(self, major: f64, minor: f64) -> Self {
+        Self {
+            major_spacing: major,
+            minor_spacing: minor,
+            ..self
+        }
+    }
+
+    /// The scale.
+    #[inline]
+    pub const fn scale(&self) -> Scale {
+        self.scale
+    }
+
+    /// The frame rate.
+    #[inline]
+    pub const fn frame_rate(&self) -> FrameRate {
+        self.frame_rate
+    }
+
+    /// The unit steps are counted in, as the fraction of units per second,
+    /// and the smallest step for major marks.
+    ///
+    /// Sixteenth beats at any tempo fit the fraction exactly.
+    fn unit(&self) -> ((u64, u64), u64) {
+        let frame_rate = self.frame_rate;
+        match self.scale {
+            Scale::Frames | Scale::Timecode { .. } => {
+                ((frame_rate.num() as _, frame_rate.den() as _), 1)
+            }
+            Scale::Seconds => ((1000, 1), 1),
+            Scale::Beats(bpm) => {
+                ((bpm.get() as u64 * BEAT_PARTS, 60), BEAT_PARTS)
+            }
+        }
+    }
+
+    /// The steps, in ascending order.
+    fn steps(&self) -> impl Iterator<Item = u64> {
+        let per_second = match self.scale {
+            Scale::Seconds => Some(1000),
+            Scale::Timecode { .. } => Some(nominal_fps(self.frame_rate)),
+            _ => None,
+        };
+        let frames = matches!(self.scale, Scale::Frames).then(decades);
+        let beats = matches!(self.scale, Scale::Beats(_))
+            .then(|| (0..u64::BITS).map(|exponent| 1 << exponent));
+
+        frames
+            .into_iter()
+            .flatten()
+            .chain(per_second.map(clock_steps).into_iter().flatten())
+            .chain(beats.into_iter().flatten())
+    }
+
+    /// The marks inside `viewport`, in ascending order.
+    pub fn marks(&self, viewport: &Viewport) -> Marks {
+        let ((num, den), smallest_major) = self.unit();
+        // Pixels between marks `step` units apart.
+        let spacing = |step: u64| {
+            step as f64 * den as f64 * TICKS_PER_SECOND as f64
+                / (num as f64 * viewport.ticks_per_pixel())
+        };
+
+        let mut major = smallest_major;
+        for step in self.steps() {
+            if smallest_major <= step {
+                major = step;
+                if self.major_spacing <= spacing(step) {
+                    break;
+                }
+            }
+        }
+        let minor = self
+            .steps()
+            .take_while(|&step| step < major)
+            .find(|&step| {
+                major.is_multiple_of(step)
+                    && self.minor_spacing <= spacing(step)
+            })
+            .unwrap_or(major);
+
+        let grid = Grid::from_spacing(
+            minor as i128 * TICKS_PER_SECOND as i128 * den as i128,
+            num as _,
+        );
+        Marks {
+            ruler: *self,
+            grid,
+            viewport: *viewport,
+            minor,
+            subdivisions: (major / minor) as _,
+            // Milliseconds need up to three decimals.
+            decimals: (0..3)
+                .find(|&decimals| major.is_multiple_of(10u64.pow(3 - decimals)))
+                .unwrap_or(3) as _,
+            front: grid.index(viewport.start, Rounding::Ceil),
+            back: grid
+                .index(viewport.end - Tick(1), Rounding::Floor)
+                .saturating_add(1),
+        }
+    }
+}
+
+/// The label of a major [`Mark`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Label {
+    /// A frame number.
+    Frame(i64),
+    /// Seconds, shown with the given number of decimals.
+    Seconds {
+        /// The time.
+        tick: Tick,
+        /// The number of decimals.
+        decimals: u8,
+    },
+    /// A timecode.
+    Timecode(Timecode),
+    /// A beat number.
+    Beat(i64),
+}
+
+impl Display for Label {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Frame(frame) | Self::Beat(frame) => write!(f, "{frame}"),
+            Self::Seconds { tick, decimals } => {
+                write!(f, "{:.*}s", *decimals as usize, tick.to_secs())
+            }
+            Self::Timecode(timecode) => write!(f, "{timecode}"),
+        }
+    }
+}
+
+/// A ruler mark.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Mark {
+    /// The first tick at or after the mark's exact time.
+    pub tick: Tick,
+    /// The position in the viewport.
+    pub pixel: f64,
+    /// Whether this is a major mark.
+    pub major: bool,
+    /// The label of a major mark. Timecodes of negative ticks have none.
+    pub label: Option<Label>,
+}
+
+/// An iterator over the [`Mark`]s of a [`Ruler`] in a [`Viewport`].
+#[derive(Clone, Debug)]
+pub struct Marks {
+    ruler: Ruler,
+    /// The grid of minor marks.
+    grid: Grid,
+    viewport: Viewport,
+    /// Units between minor marks.
+    minor: u64,
+    /// Minor marks per major mark.
+    subdivisions: i64,
+    decimals: u8,
+    front: i64,
+    back: i64,
+}
+
+impl Marks {
+    fn label(&self, index: i64, tick: Tick) -> Option<Label> {
+        let frame_rate = self.ruler.frame_rate;
+        match self.ruler.scale {
+            Scale::Frames => {
+                Some(Label::Frame(frame_containing(tick, frame_rate)))
+            }
+            Scale::Seconds => Some(Label::Seconds {
+                tick,
+                decimals: self.decimals,
+            }),
+            Scale::Timecode { drop_frame } => Timecode::from_tick(
+                tick,
+                frame_rate,
+                drop_frame,
+                Rounding::Floor,
+            )
+            .ok()
+            .map(Label::Timecode),
+            Scale::Beats(_) => Some(Label::Beat(
+                index.saturating_mul(self.minor as _) / BEAT_PARTS as i64,
+            )),
+        }
+    }
+}
+
+impl Iterator for Marks {
+    type Item = Mark;
+
+    fn next(&mut self) -> Option<Mark> {
+        if self.back <= self.front {
+            return None;
+        }
+        let index = self.front;
+        self.front += 1;
+
+        let tick = self.grid.point(index);
+        let major = 0 == index.rem_euclid(self.subdivisions);
+        Some(Mark {
+            tick,
+            pixel: self.viewport.to_pixel(tick),
+            major,
+            label: major.then(|| self.label(index, tick)).flatten(),
+        })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for Marks {
+    #[inline]
+    fn len(&self) -> usize {
+        usize::try_from(self.back.saturating_sub(self.front)).unwrap_or(0)
+    }
+}
+
+impl FusedIterator for Marks {}
+
+/// A range of ticks shown on a row of pixels.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Viewport {
+    start: Tick,
+    end: Tick,
+    width: f64,
+}
+
+impl Viewport {
+    /// Shows `visible` on `width` pixels.
+    ///
+    /// Returns `None` if `visible` is empty or `width` is not positive and
+    /// finite.
+    pub fn new(visible: Range<Tick>, width: f64) -> Option<Self> {
+        (!visible.is_empty() && 0.0 < width && width.is_finite()).then_some(
+            Self {
+                start: visible.start,
+                end: visible.end,
+                width,
+            },
+        )
+    }
+
+    /// The visible ticks.
+    #[inline]
+    pub fn visible(&self) -> Range<Tick> {
+        self.start..self.end
+    }
+
+    /// The width in pixels.
+    #[inline]
+    pub fn width(&self) -> f64 {
+        self.width
+    }
+
+    /// The number of ticks per pixel.
+    #[inline]
+    pub fn ticks_per_pixel(&self) -> f64 {
+        (self.end - self.start).0 as f64 / self.width
+    }
+
+    /// The position of `tick`, in pixels from the left edge.
+    #[inline]
+    pub fn to_pixel(&self, tick: Tick) -> f64 {
+        (tick - self.start).0 as f64 / self.ticks_per_pixel()
+    }
+
+    /// The tick nearest to `pixel`.
+    #[inline]
+    pub fn to_tick(&self, pixel: f64) -> Tick {
+        self.start + Tick::from(pixel * self.ticks_per_pixel())
+    }
+
+    /// Shows `factor` times fewer ticks, keeping the tick at `anchor`, e.g.
+    /// the mouse cursor, at the same pixel.
+    ///
+    /// A `factor` above one zooms in. At least one tick stays visible; a
+    /// `factor` that is not positive and finite is ignored.
+    pub fn zoom(&mut self, factor: f64, anchor: f64) {
+        if !(0.0 < factor && factor.is_finite()) {
+            return;
+        }
+        let anchor_tick = self.to_tick(anchor);
+        let span = (self.end - self.start).0 as f64 / factor;
+        let span =
+            Tick::from(span.clamp(1.0, i64::MAX as f64 / 2.0)).max(Tick(1));
+        self.start =
+            anchor_tick - Tick::from(anchor / self.width * span.0 as f64);
+        self.end = self.start + span;
+    }
+
+    /// Moves the view `pixels` to the right, i.e. shows later ticks for a
+    /// positive `pixels`.
+    #[inline]
+    pub fn pan(&mut self, pixels: f64) {
+        let offset = Tick::from(pixels * self.ticks_per_pixel());
+        self.start += offset;
+        self.end += offset;
+    }
+}
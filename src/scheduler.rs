@@ -0,0 +1,394 @@
+//! Queues of events due at exact ticks.
+//!
+//! A [`Scheduler`] pops events in tick order, or in reverse for backward
+//! playback. A [`TimerWheel`] only moves forward but scales to very large
+//! numbers of timers.
+//!
+//! Both are deterministic: events due at the same tick pop in the order
+//! they were scheduled, or the exact reverse of that when popping
+//! backward. A rescheduled event counts as scheduled when it was
+//! rescheduled.
+//!
+//! # Examples
+//!
+//! ```
+//! use frame_tick::{Tick, scheduler::Scheduler};
+//!
+//! let second = Tick::from_secs_int(1);
+//! let mut cues = Scheduler::new();
+//! let fade = cues.schedule(second * 2, "fade");
+//! cues.schedule(second, "a");
+//! cues.schedule(second, "b");
+//! cues.schedule(second * 3, "cut");
+//!
+//! cues.reschedule(fade, second);
+//! assert_eq!(
+//!     ["a", "b", "fade"],
+//!     cues.due(second).map(|due| due.event).collect::<Vec<_>>()[..]
+//! );
+//!
+//! // Playing backward from the end.
+//! cues.schedule(Tick::new(0), "start");
+//! assert_eq!(
+//!     ["cut", "start"],
+//!     cues.due_rev(Tick::new(0))
+//!         .map(|due| due.event)
+//!         .collect::<Vec<_>>()[..]
+//! );
+//! ```
+use crate::Tick;
+use alloc::{collections::BTreeMap, vec::Vec};
+
+/// A handle to a scheduled event, to cancel or reschedule it.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct TimerId(u64);
+
+/// An event that became due.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Due<E> {
+    /// The handle the event was scheduled with.
+    pub id: TimerId,
+    /// The tick the event was due at.
+    pub at: Tick,
+    /// The event.
+    pub event: E,
+}
+
+/// A double-ended priority queue of events keyed by tick, see the
+/// [module documentation](self).
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Scheduler<E> {
+    /// Events by tick and sequence number.
+    queue: BTreeMap<(Tick, u64), (TimerId, E)>,
+    /// The key of each event in `queue`.
+    timers: BTreeMap<TimerId, (Tick, u64)>,
+    sequence: u64,
+}
+
+impl<E> Default for Scheduler<E> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E> Scheduler<E> {
+    /// An empty scheduler.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            queue: BTreeMap::new(),
+            timers: BTreeMap::new(),
+            sequence: 0,
+        }
+    }
+
+    /// The number of scheduled events.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Returns `true` if no events are scheduled.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Schedules `event` at `at`.
+    pub fn schedule(&mut self, at: Tick, event: E) -> TimerId {
+        let id = TimerId(self.sequence);
+        self.insert(id, at, event);
+        id
+    }
+
+    fn insert(&mut self, id: TimerId, at: Tick, event: E) {
+        let key = (at, self.sequence);
+        self.sequence += 1;
+        self.queue.insert(key, (id, event));
+        self.timers.insert(id, key);
+    }
+
+    /// Removes the event `id` and returns it, unless it already fired or
+    /// was cancelled.
+    pub fn cancel(&mut self, id: TimerId) -> Option<E> {
+        let key = self.timers.remove(&id)?;
+        self.queue.remove(&key).map(|(_, event)| event)
+    }
+
+    /// Moves the event `id` to `at`. Returns `false` if it already fired or
+    /// was cancelled.
+    pub fn reschedule(&mut self, id: TimerId, at: Tick) -> bool {
+        match self.cancel(id) {
+            Some(event) => {
+                self.insert(id, at, event);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The tick the event `id` is due at.
+    #[inline]
+    pub fn due_at(&self, id: TimerId) -> Option<Tick> {
+        self.timers.get(&id).map(|&(at, _)| at)
+    }
+
+    /// The event `id`.
+    #[inline]
+    pub fn get(&self, id: TimerId) -> Option<&E> {
+        let key = self.timers.get(&id)?;
+        self.queue.get(key).map(|(_, event)| event)
+    }
+
+    /// The first event and its tick.
+    #[inline]
+    pub fn first(&self) -> Option<(Tick, &E)> {
+        self.queue
+            .first_key_value()
+            .map(|(&(at, _), (_, event))| (at, event))
+    }
+
+    /// The last event and its tick.
+    #[inline]
+    pub fn last(&self) -> Option<(Tick, &E)> {
+        self.queue
+            .last_key_value()
+            .map(|(&(at, _), (_, event))| (at, event))
+    }
+
+    fn fire(
+        &mut self,
+        ((at, _), (id, event)): ((Tick, u64), (TimerId, E)),
+    ) -> Due<E> {
+        self.timers.remove(&id);
+        Due { id, at, event }
+    }
+
+    /// Removes and returns the first event due at or before `until`.
+    pub fn pop_due(&mut self, until: Tick) -> Option<Due<E>> {
+        let entry = self.queue.first_entry().filter(|e| e.key().0 <= until)?;
+        let entry = entry.remove_entry();
+        Some(self.fire(entry))
+    }
+
+    /// Removes and returns the last event due at or after `until`, for
+    /// backward playback.
+    pub fn pop_due_rev(&mut self, until: Tick) -> Option<Due<E>> {
+        let entry = self.queue.last_entry().filter(|e| until <= e.key().0)?;
+        let entry = entry.remove_entry();
+        Some(self.fire(entry))
+    }
+
+    /// Removes and returns all events due at or before `until`, in order.
+    #[inline]
+    pub fn due(&mut self, until: Tick) -> impl Iterator<Item = Due<E>> + '_ {
+        core::iter::from_fn(move || self.pop_due(until))
+    }
+
+    /// Removes and returns all events due at or after `until`, in reverse
+    /// order.
+    #[inline]
+    pub fn due_rev(
+        &mut self,
+        until: Tick,
+    ) -> impl Iterator<Item = Due<E>> + '_ {
+        core::iter::from_fn(move || self.pop_due_rev(until))
+    }
+}
+
+/// Bits of a bucket number per wheel level.
+const LEVEL_BITS: u32 = 6;
+const SLOTS: usize = 1 << LEVEL_BITS;
+const LEVELS: usize = u64::BITS.div_ceil(LEVEL_BITS) as usize;
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+struct Entry<E> {
+    at: Tick,
+    sequence: u64,
+    id: TimerId,
+    event: E,
+}
+
+/// A hierarchical timer wheel, see the [module documentation](self).
+///
+/// Time is split into buckets of `granularity` ticks. Each level of the
+/// wheel has 64 slots, each covering 64 slots of the level below. Events
+/// move down a level whenever the wheel reaches their slot, so scheduling
+/// and cancelling only touch a single slot, no matter how many events are
+/// scheduled. Popping sorts the events of one bucket at a time.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct TimerWheel<E> {
+    granularity: i64,
+    /// The bucket being popped. Events of this and earlier buckets are in
+    /// `ready`.
+    current: u64,
+    /// `LEVELS` levels of `SLOTS` slots each.
+    slots: Vec<Vec<Entry<E>>>,
+    /// The events of the current and earlier buckets, sorted in reverse.
+    ready: Vec<Entry<E>>,
+    /// The tick and sequence number of each event.
+    timers: BTreeMap<TimerId, (Tick, u64)>,
+    sequence: u64,
+}
+
+impl<E> TimerWheel<E> {
+    /// An empty wheel with buckets of `granularity` ticks, starting at
+    /// `start`.
+    ///
+    /// Returns `None` if `granularity` is not positive.
+    pub fn new(granularity: Tick, start: Tick) -> Option<Self> {
+        (0 < granularity.0).then(|| {
+            let mut wheel = Self {
+                granularity: granularity.0,
+                current: 0,
+                slots: (0..LEVELS * SLOTS).map(|_| Vec::new()).collect(),
+                ready: Vec::new(),
+                timers: BTreeMap::new(),
+                sequence: 0,
+            };
+            wheel.current = wheel.bucket(start);
+            wheel
+        })
+    }
+
+    /// The number of scheduled events.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.timers.len()
+    }
+
+    /// Returns `true` if no events are scheduled.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.timers.is_empty()
+    }
+
+    /// The bucket of `at`, offset so buckets of negative ticks sort first.
+    fn bucket(&self, at: Tick) -> u64 {
+        at.0.div_euclid(self.granularity) as u64 ^ (1 << 63)
+    }
+
+    /// The first tick of `bucket`.
+    fn bucket_start(&self, bucket: u64) -> i128 {
+        ((bucket ^ (1 << 63)) as i64) as i128 * self.granularity as i128
+    }
+
+    /// The slot of `bucket`, or `None` if it is ready.
+    fn slot(&self, bucket: u64) -> Option<usize> {
+        let differing = bucket ^ self.current;
+        (self.current < bucket).then(|| {
+            let level =
+                (u64::BITS - 1 - differing.leading_zeros()) / LEVEL_BITS;
+            let slot = (bucket >> (level * LEVEL_BITS)) as usize % SLOTS;
+            level as usize * SLOTS + slot
+        })
+    }
+
+    fn insert(&mut self, entry: Entry<E>) {
+        match self.slot(self.bucket(entry.at)) {
+            Some(slot) => self.slots[slot].push(entry),
+            None => {
+                let key = (entry.at, entry.sequence);
+                let index =
+                    self.ready.partition_point(|e| key < (e.at, e.sequence));
+                self.ready.insert(index, entry);
+            }
+        }
+    }
+
+    /// Schedules `event` at `at`.
+    pub fn schedule(&mut self, at: Tick, event: E) -> TimerId {
+        let id = TimerId(self.sequence);
+        self.insert_new(id, at, event);
+        id
+    }
+
+    fn insert_new(&mut self, id: TimerId, at: Tick, event: E) {
+        let sequence = self.sequence;
+        self.sequence += 1;
+        self.timers.insert(id, (at, sequence));
+        self.insert(Entry {
+            at,
+            sequence,
+            id,
+            event,
+        });
+    }
+
+    /// Removes the event `id` and returns it, unless it already fired or
+    /// was cancelled.
+    pub fn cancel(&mut self, id: TimerId) -> Option<E> {
+        let (at, sequence) = self.timers.remove(&id)?;
+        let entries = match self.slot(self.bucket(at)) {
+            Some(slot) => &mut self.slots[slot],
+            None => &mut self.ready,
+        };
+        let index = entries.iter().position(|e| e.sequence == sequence)?;
+        Some(entries.remove(index).event)
+    }
+
+    /// Moves the event `id` to `at`. Returns `false` if it already fired or
+    /// was cancelled.
+    pub fn reschedule(&mut self, id: TimerId, at: Tick) -> bool {
+        match self.cancel(id) {
+            Some(event) => {
+                self.insert_new(id, at, event);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// The tick the event `id` is due at.
+    #[inline]
+    pub fn due_at(&self, id: TimerId) -> Option<Tick> {
+        self.timers.get(&id).map(|&(at, _)| at)
+    }
+
+    /// The next bucket with events and the slot holding them.
+    fn next_bucket(&self) -> Option<(u64, usize)> {
+        (0..LEVELS).find_map(|level| {
+            let shift = level as u32 * LEVEL_BITS;
+            let current = (self.current >> shift) as usize % SLOTS;
+            (current + 1..SLOTS).find_map(|slot| {
+                let index = level * SLOTS + slot;
+                (!self.slots[index].is_empty()).then(|| {
+                    let above = self.current.checked_shr(shift + LEVEL_BITS);
+                    let above =
+                        above.map_or(0, |above| above << (shift + LEVEL_BITS));
+                    (above | ((slot as u64) << shift), index)
+                })
+            })
+        })
+    }
+
+    /// Removes and returns the first event due at or before `until`.
+    pub fn pop_due(&mut self, until: Tick) -> Option<Due<E>> {
+        loop {
+            if let Some(entry) = self.ready.last() {
+                if until < entry.at {
+                    return None;
+                }
+                let Entry { at, id, event, .. } = self.ready.pop()?;
+                self.timers.remove(&id);
+                return Some(Due { id, at, event });
+            }
+            let (bucket, index) = self.next_bucket()?;
+            if (until.0 as i128) < self.bucket_start(bucket) {
+                return None;
+            }
+            // Move the slot's events down a level, or into `ready`.
+            self.current = bucket;
+            for entry in core::mem::take(&mut self.slots[index]) {
+                self.insert(entry);
+            }
+        }
+    }
+
+    /// Removes and returns all events due at or before `until`, in order.
+    #[inline]
+    pub fn due(&mut self, until: Tick) -> impl Iterator<Item = Due<E>> + '_ {
+        core::iter::from_fn(move || self.pop_due(until))
+    }
+}
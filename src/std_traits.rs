@@ -1,7 +1,7 @@
-use crate::Tick;
+use crate::{DurationError, NANOS_PER_SECOND, Rounding, Tick};
 use std::{
     fmt::{Display, Error, Formatter},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 impl Display for Tick {
@@ -10,15 +10,95 @@ impl Display for Tick {
     }
 }
 
-impl From<Duration> for Tick {
-    fn from(duration: Duration) -> Self {
-        let secs = duration.as_secs_f64();
-        Self::from_secs(secs)
+impl TryFrom<Duration> for Tick {
+    type Error = DurationError;
+
+    /// Rounds to the nearest tick, see [`Tick::from_duration()`].
+    fn try_from(duration: Duration) -> Result<Self, Self::Error> {
+        Self::from_duration(duration, Rounding::Nearest)
     }
 }
 
-impl From<Tick> for Duration {
-    fn from(tick: Tick) -> Self {
-        Duration::from_secs_f64(tick.to_secs())
+impl TryFrom<Tick> for Duration {
+    type Error = DurationError;
+
+    /// Rounds to the nearest nanosecond, see [`Tick::to_duration()`].
+    fn try_from(tick: Tick) -> Result<Self, Self::Error> {
+        tick.to_duration(Rounding::Nearest)
+    }
+}
+
+impl Tick {
+    /// Create ticks from a [`Duration`].
+    ///
+    /// This is exact up to the final rounding from nanoseconds to ticks.
+    pub fn from_duration(
+        duration: Duration,
+        rounding: Rounding,
+    ) -> Result<Self, DurationError> {
+        Self::from_nanos(
+            duration.as_secs() as i128 * NANOS_PER_SECOND
+                + duration.subsec_nanos() as i128,
+            rounding,
+        )
+    }
+
+    /// Convert ticks to a [`Duration`].
+    ///
+    /// Fails for negative ticks. Ticks that do not land on a nanosecond are
+    /// rounded as requested.
+    pub fn to_duration(
+        self,
+        rounding: Rounding,
+    ) -> Result<Duration, DurationError> {
+        let nanos = self.to_nanos(rounding);
+        if nanos < 0 {
+            return Err(DurationError::Negative);
+        }
+
+        Ok(Duration::new(
+            (nanos / NANOS_PER_SECOND) as _,
+            (nanos % NANOS_PER_SECOND) as _,
+        ))
+    }
+
+    /// The offset of `instant` from a reference `epoch`.
+    ///
+    /// This is negative if `instant` is before `epoch`.
+    pub fn from_instant(
+        instant: Instant,
+        epoch: Instant,
+        rounding: Rounding,
+    ) -> Result<Self, DurationError> {
+        match instant.checked_duration_since(epoch) {
+            Some(after) => Self::from_duration(after, rounding),
+            None => {
+                let before = epoch.duration_since(instant);
+                let nanos = before.as_secs() as i128 * NANOS_PER_SECOND
+                    + before.subsec_nanos() as i128;
+                Self::from_nanos(-nanos, rounding)
+            }
+        }
+    }
+
+    /// The instant that is this tick's offset away from a reference
+    /// `epoch`.
+    pub fn to_instant(
+        self,
+        epoch: Instant,
+        rounding: Rounding,
+    ) -> Result<Instant, DurationError> {
+        let nanos = self.to_nanos(rounding);
+        let offset = Duration::new(
+            (nanos.unsigned_abs() / NANOS_PER_SECOND as u128) as _,
+            (nanos.unsigned_abs() % NANOS_PER_SECOND as u128) as _,
+        );
+
+        if nanos < 0 {
+            epoch.checked_sub(offset)
+        } else {
+            epoch.checked_add(offset)
+        }
+        .ok_or(DurationError::Overflow)
     }
 }
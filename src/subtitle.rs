@@ -0,0 +1,628 @@
+//! Subtitle and caption timestamps.
+//!
+//! Supported are SRT (`HH:MM:SS,mmm`), WebVTT (`[HH:]MM:SS.mmm`), TTML clock
+//! and offset time expressions and SCC drop-frame timecodes
+//! (`HH:MM:SS;FF`).
+//!
+//! None of the millisecond based formats can express a [`Tick`] exactly. So
+//! every conversion goes through a [`RoundingPolicy`] that decides how a
+//! time is rounded and, optionally, which frame grid it is snapped to.
+//!
+//! # Examples
+//!
+//! ```
+//! # use core::num::NonZeroU32;
+//! use frame_tick::{
+//!     FrameRate, FrameRateConversion, Rounding, Tick,
+//!     subtitle::{self, RoundingPolicy},
+//! };
+//!
+//! // Snap cues to the start of the frame they fall into at 24 fps.
+//! let policy =
+//!     RoundingPolicy::new(Rounding::Floor).with_frame_rate(FrameRate::FILM);
+//!
+//! let tick = subtitle::parse_srt("00:00:01,050", policy).unwrap();
+//! // Frame 25 starts at 1.0417s.
+//! assert_eq!(Tick::from_frames(25, NonZeroU32::new(24).unwrap()), tick);
+//! assert_eq!(
+//!     "00:00:01.041",
+//!     subtitle::format_webvtt(tick, policy).unwrap().to_string()
+//! );
+//! ```
+use crate::{
+    FrameRate, Rounding, TICKS_PER_SECOND, Tick, div_round, frame_start,
+    parse_digits,
+    timecode::{Timecode, TimecodeError, nominal_fps},
+};
+use core::fmt::{self, Display, Formatter};
+
+/// An error parsing or formatting a subtitle timestamp.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum SubtitleError {
+    /// The string does not match the grammar of the format.
+    Syntax,
+    /// A field is out of range, e.g. 61 minutes.
+    OutOfRange,
+    /// The format can not express negative times.
+    Negative,
+    /// The result does not fit.
+    Overflow,
+}
+
+impl Display for SubtitleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Syntax => write!(f, "malformed subtitle timestamp"),
+            Self::OutOfRange => {
+                write!(f, "subtitle timestamp field out of range")
+            }
+            Self::Negative => {
+                write!(f, "subtitle timestamps can not be negative")
+            }
+            Self::Overflow => write!(f, "subtitle timestamp overflows"),
+        }
+    }
+}
+
+impl core::error::Error for SubtitleError {}
+
+impl From<TimecodeError> for SubtitleError {
+    fn from(error: TimecodeError) -> Self {
+        match error {
+            TimecodeError::Syntax => Self::Syntax,
+            TimecodeError::OutOfRange | TimecodeError::DropFrameUnsupported => {
+                Self::OutOfRange
+            }
+            TimecodeError::Negative => Self::Negative,
+            TimecodeError::Overflow => Self::Overflow,
+        }
+    }
+}
+
+/// How times are rounded when converting between a subtitle format and
+/// [`Tick`]s.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct RoundingPolicy {
+    /// If set, times are snapped to the first tick of a frame at this rate.
+    pub frame_rate: Option<FrameRate>,
+    /// How to round onto the frame grid, ticks or the display unit of the
+    /// format.
+    pub rounding: Rounding,
+}
+
+impl RoundingPolicy {
+    /// Round with `rounding` but do not snap to a frame grid.
+    #[inline]
+    pub fn new(rounding: Rounding) -> Self {
+        Self {
+            frame_rate: None,
+            rounding,
+        }
+    }
+
+    /// Snap to the frame grid of `frame_rate`.
+    #[inline]
+    pub fn with_frame_rate(self, frame_rate: FrameRate) -> Self {
+        Self {
+            frame_rate: Some(frame_rate),
+            ..self
+        }
+    }
+}
+
+/// The time bases of a TTML document.
+///
+/// These correspond to the `ttp:frameRate`, `ttp:frameRateMultiplier`,
+/// `ttp:subFrameRate` and `ttp:tickRate` attributes.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct TtmlTimeBase {
+    /// The effective frame rate, i.e. `frameRate * frameRateMultiplier`.
+    pub frame_rate: FrameRate,
+    /// The number of sub-frames per frame.
+    pub sub_frame_rate: u32,
+    /// The number of ticks (TTML's, not ours) per second.
+    pub tick_rate: u32,
+}
+
+impl TtmlTimeBase {
+    /// Create a time base from the TTML parameter attributes, applying the
+    /// defaults of the TTML specification for missing ones.
+    ///
+    /// Returns `None` if any of the values is zero.
+    pub fn from_attributes(
+        frame_rate: Option<u32>,
+        frame_rate_multiplier: Option<(u32, u32)>,
+        sub_frame_rate: Option<u32>,
+        tick_rate: Option<u32>,
+    ) -> Option<Self> {
+        let (multiplier_num, multiplier_den) =
+            frame_rate_multiplier.unwrap_or((1, 1));
+        let sub_frame_rate = sub_frame_rate.unwrap_or(1);
+        let tick_rate = match (tick_rate, frame_rate) {
+            (Some(tick_rate), _) => tick_rate,
+            (None, Some(frame_rate)) => {
+                frame_rate.checked_mul(sub_frame_rate)?
+            }
+            (None, None) => 1,
+        };
+        let frame_rate = frame_rate.unwrap_or(30);
+
+        (0 != sub_frame_rate && 0 != tick_rate).then_some(())?;
+
+        Some(Self {
+            frame_rate: FrameRate::from_u64(
+                frame_rate as u64 * multiplier_num as u64,
+                multiplier_den as u64,
+            )?,
+            sub_frame_rate,
+            tick_rate,
+        })
+    }
+}
+
+impl Default for TtmlTimeBase {
+    fn default() -> Self {
+        Self {
+            frame_rate: FrameRate::FPS_30,
+            sub_frame_rate: 1,
+            tick_rate: 1,
+        }
+    }
+}
+
+/// How [`format_ttml()`] writes a time expression.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub enum TtmlStyle {
+    /// Clock time with milliseconds, `HH:MM:SS.mmm`.
+    #[default]
+    Clock,
+    /// Clock time with frames, `HH:MM:SS:FF`.
+    ClockFrames,
+    /// Offset time in frames, e.g. `42f`.
+    Frames,
+    /// Offset time in TTML ticks, e.g. `420t`.
+    Ticks,
+}
+
+/// A formatted subtitle timestamp.
+///
+/// Created by the `format_*` functions of this module; use its [`Display`]
+/// implementation to get the text.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SubtitleTimestamp(Repr);
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+enum Repr {
+    Clock { millis: u64, separator: char },
+    Timecode(Timecode),
+    Offset { count: u64, metric: char },
+}
+
+impl Display for SubtitleTimestamp {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Repr::Clock { millis, separator } => write!(
+                f,
+                "{:02}:{:02}:{:02}{separator}{:03}",
+                millis / 3_600_000,
+                millis / 60_000 % 60,
+                millis / 1000 % 60,
+                millis % 1000
+            ),
+            Repr::Timecode(timecode) => timecode.fmt(f),
+            Repr::Offset { count, metric } => write!(f, "{count}{metric}"),
+        }
+    }
+}
+
+/// Parse an SRT timestamp, `HH:MM:SS,mmm`.
+pub fn parse_srt(
+    s: &str,
+    policy: RoundingPolicy,
+) -> Result<Tick, SubtitleError> {
+    let (clock, millis) = s.split_once(',').ok_or(SubtitleError::Syntax)?;
+    let mut fields = clock.split(':');
+    let (Some(hours), Some(minutes), Some(seconds), None) =
+        (fields.next(), fields.next(), fields.next(), fields.next())
+    else {
+        return Err(SubtitleError::Syntax);
+    };
+
+    clock_millis(hours, minutes, seconds, millis)?.to_tick(policy)
+}
+
+/// Format `tick` as an SRT timestamp, `HH:MM:SS,mmm`.
+pub fn format_srt(
+    tick: Tick,
+    policy: RoundingPolicy,
+) -> Result<SubtitleTimestamp, SubtitleError> {
+    Ok(SubtitleTimestamp(Repr::Clock {
+        millis: Seconds::from_tick(tick, policy)?.count(1, 1000, policy)?,
+        separator: ',',
+    }))
+}
+
+/// Parse a WebVTT timestamp, `[HH:]MM:SS.mmm`.
+pub fn parse_webvtt(
+    s: &str,
+    policy: RoundingPolicy,
+) -> Result<Tick, SubtitleError> {
+    let (clock, millis) = s.split_once('.').ok_or(SubtitleError::Syntax)?;
+    let mut fields = clock.rsplit(':');
+    let (Some(seconds), Some(minutes), hours, None) =
+        (fields.next(), fields.next(), fields.next(), fields.next())
+    else {
+        return Err(SubtitleError::Syntax);
+    };
+
+    clock_millis(hours.unwrap_or("00"), minutes, seconds, millis)?
+        .to_tick(policy)
+}
+
+/// Format `tick` as a WebVTT timestamp, `HH:MM:SS.mmm`.
+pub fn format_webvtt(
+    tick: Tick,
+    policy: RoundingPolicy,
+) -> Result<SubtitleTimestamp, SubtitleError> {
+    Ok(SubtitleTimestamp(Repr::Clock {
+        millis: Seconds::from_tick(tick, policy)?.count(1, 1000, policy)?,
+        separator: '.',
+    }))
+}
+
+/// Parse a TTML time expression.
+///
+/// Accepted are clock times (`HH:MM:SS`, `HH:MM:SS.fff`, `HH:MM:SS:FF` and
+/// `HH:MM:SS:FF.S`) and offset times with the metrics `h`, `m`, `s`, `ms`,
+/// `f` and `t` (e.g. `1.5s`, `42f`, `1001t`).
+pub fn parse_ttml(
+    s: &str,
+    time_base: TtmlTimeBase,
+    policy: RoundingPolicy,
+) -> Result<Tick, SubtitleError> {
+    let frame_rate = time_base.frame_rate;
+    let frame = Seconds::new(frame_rate.den() as _, frame_rate.num() as _);
+
+    let seconds = if s.contains(':') {
+        let mut fields = s.split(':');
+        let (Some(hours), Some(minutes), Some(seconds), frames, None) = (
+            fields.next(),
+            fields.next(),
+            fields.next(),
+            fields.next(),
+            fields.next(),
+        ) else {
+            return Err(SubtitleError::Syntax);
+        };
+
+        match frames {
+            None => {
+                let (seconds, fraction) =
+                    seconds.split_once('.').unwrap_or((seconds, ""));
+                let clock = clock(hours, minutes, seconds)?;
+                if fraction.is_empty() {
+                    clock
+                } else {
+                    clock.add(parse_decimal("0", fraction)?)?
+                }
+            }
+            Some(frames) => {
+                let (frames, sub_frames) =
+                    frames.split_once('.').unwrap_or((frames, "0"));
+                let frames = digits(frames)?;
+                let sub_frames = digits(sub_frames)?;
+                if nominal_fps(frame_rate) <= frames
+                    || time_base.sub_frame_rate as u64 <= sub_frames
+                {
+                    return Err(SubtitleError::OutOfRange);
+                }
+
+                // (frames + sub_frames / sub_frame_rate) * frame.
+                let frames = Seconds::new(
+                    frames as i128 * time_base.sub_frame_rate as i128
+                        + sub_frames as i128,
+                    time_base.sub_frame_rate as _,
+                );
+                clock(hours, minutes, seconds)?.add(frames.mul(frame)?)?
+            }
+        }
+    } else {
+        let (value, unit) = if let Some(value) = s.strip_suffix("ms") {
+            (value, Seconds::new(1, 1000))
+        } else {
+            let metric = s.chars().last().ok_or(SubtitleError::Syntax)?;
+            let unit = match metric {
+                'h' => Seconds::new(3600, 1),
+                'm' => Seconds::new(60, 1),
+                's' => Seconds::new(1, 1),
+                'f' => frame,
+                't' => Seconds::new(1, time_base.tick_rate as _),
+                _ => return Err(SubtitleError::Syntax),
+            };
+            (&s[..s.len() - 1], unit)
+        };
+        let (integer, fraction) = value.split_once('.').unwrap_or((value, ""));
+
+        parse_decimal(integer, fraction)?.mul(unit)?
+    };
+
+    seconds.to_tick(policy)
+}
+
+/// Format `tick` as a TTML time expression in the given `style`.
+pub fn format_ttml(
+    tick: Tick,
+    time_base: TtmlTimeBase,
+    style: TtmlStyle,
+    policy: RoundingPolicy,
+) -> Result<SubtitleTimestamp, SubtitleError> {
+    let seconds = Seconds::from_tick(tick, policy)?;
+    let frame_rate = time_base.frame_rate;
+
+    Ok(SubtitleTimestamp(match style {
+        TtmlStyle::Clock => Repr::Clock {
+            millis: seconds.count(1, 1000, policy)?,
+            separator: '.',
+        },
+        TtmlStyle::ClockFrames => Repr::Timecode(clock_frames(
+            seconds.count(frame_rate.den(), frame_rate.num(), policy)?,
+            frame_rate,
+            policy.rounding,
+        )?),
+        TtmlStyle::Frames => Repr::Offset {
+            count: seconds.count(frame_rate.den(), frame_rate.num(), policy)?,
+            metric: 'f',
+        },
+        TtmlStyle::Ticks => Repr::Offset {
+            count: seconds.count(1, time_base.tick_rate, policy)?,
+            metric: 't',
+        },
+    }))
+}
+
+/// The TTML clock value of `frame`, i.e. elapsed seconds plus frames.
+///
+/// At 1000/1001 rates seconds do not start on a frame, so the seconds are
+/// the last whose start, counted in frames with `rounding`, is not after
+/// `frame`. Parsing the result with the same rounding gives `frame` again.
+fn clock_frames(
+    frame: u64,
+    frame_rate: FrameRate,
+    rounding: Rounding,
+) -> Result<Timecode, SubtitleError> {
+    let (frame, num, den) = (
+        frame as i128,
+        frame_rate.num() as i128,
+        frame_rate.den() as i128,
+    );
+    let start = |second: i128| div_round(second * num, den, rounding);
+
+    let mut second = frame * den / num;
+    if start(second + 1) <= frame {
+        second += 1;
+    }
+
+    Ok(Timecode::new(
+        u32::try_from(second / 3600).map_err(|_| SubtitleError::Overflow)?,
+        (second / 60 % 60) as _,
+        (second % 60) as _,
+        (frame - start(second)) as _,
+        false,
+    ))
+}
+
+/// Parse an SCC timecode at 29.97 fps, `HH:MM:SS;FF` (drop-frame) or
+/// `HH:MM:SS:FF` (non-drop-frame).
+pub fn parse_scc(
+    s: &str,
+    policy: RoundingPolicy,
+) -> Result<Tick, SubtitleError> {
+    let frame = s.parse::<Timecode>()?.to_frame_number(FrameRate::NTSC)?;
+
+    Seconds::new(frame as i128 * 1001, 30000).to_tick(policy)
+}
+
+/// Format `tick` as a drop-frame SCC timecode at 29.97 fps, `HH:MM:SS;FF`.
+pub fn format_scc(
+    tick: Tick,
+    policy: RoundingPolicy,
+) -> Result<SubtitleTimestamp, SubtitleError> {
+    let frame = Seconds::from_tick(tick, policy)?.count(1001, 30000, policy)?;
+
+    Ok(SubtitleTimestamp(Repr::Timecode(
+        Timecode::from_frame_number(frame, FrameRate::NTSC, true)?,
+    )))
+}
+
+/// An exact, non-negative rational number of seconds.
+#[derive(Copy, Clone, Debug)]
+struct Seconds {
+    numer: i128,
+    denom: i128,
+}
+
+impl Seconds {
+    #[inline]
+    fn new(numer: i128, denom: i128) -> Self {
+        Self { numer, denom }
+    }
+
+    /// The time of `tick`, snapped to the frame grid of `policy`.
+    fn from_tick(
+        tick: Tick,
+        policy: RoundingPolicy,
+    ) -> Result<Self, SubtitleError> {
+        if tick.0 < 0 {
+            return Err(SubtitleError::Negative);
+        }
+
+        Ok(match policy.frame_rate {
+            Some(frame_rate) => {
+                let (num, den) = (
+                    frame_rate.num() as i128,
+                    TICKS_PER_SECOND as i128 * frame_rate.den() as i128,
+                );
+                let frame = match policy.rounding {
+                    // A frame starts on the first tick at or after its exact
+                    // start, so it is the first frame after the tick before.
+                    Rounding::Ceil => {
+                        div_round(
+                            (tick.0 as i128 - 1) * num,
+                            den,
+                            Rounding::Floor,
+                        ) + 1
+                    }
+                    rounding => div_round(tick.0 as i128 * num, den, rounding),
+                };
+                Self::new(
+                    frame * frame_rate.den() as i128,
+                    frame_rate.num() as _,
+                )
+            }
+            None => Self::new(tick.0 as _, TICKS_PER_SECOND as _),
+        })
+    }
+
+    fn add(self, other: Self) -> Result<Self, SubtitleError> {
+        let numer = self
+            .numer
+            .checked_mul(other.denom)
+            .zip(other.numer.checked_mul(self.denom))
+            .and_then(|(a, b)| a.checked_add(b));
+
+        Ok(Self::new(
+            numer.ok_or(SubtitleError::Overflow)?,
+            self.denom
+                .checked_mul(other.denom)
+                .ok_or(SubtitleError::Overflow)?,
+        ))
+    }
+
+    fn mul(self, other: Self) -> Result<Self, SubtitleError> {
+        Ok(Self::new(
+            self.numer
+                .checked_mul(other.numer)
+                .ok_or(SubtitleError::Overflow)?,
+            self.denom
+                .checked_mul(other.denom)
+                .ok_or(SubtitleError::Overflow)?,
+        ))
+    }
+
+    /// The number of units of `numer / denom` seconds, rounded by `policy`.
+    fn count(
+        self,
+        numer: u32,
+        denom: u32,
+        policy: RoundingPolicy,
+    ) -> Result<u64, SubtitleError> {
+        let count = div_round(
+            self.numer
+                .checked_mul(denom as _)
+                .ok_or(SubtitleError::Overflow)?,
+            self.denom
+                .checked_mul(numer as _)
+                .ok_or(SubtitleError::Overflow)?,
+            policy.rounding,
+        );
+
+        u64::try_from(count).map_err(|_| SubtitleError::Overflow)
+    }
+
+    /// Round to a tick, snapping to the frame grid of `policy`.
+    fn to_tick(self, policy: RoundingPolicy) -> Result<Tick, SubtitleError> {
+        let ticks = match policy.frame_rate {
+            Some(frame_rate) => {
+                let frame = div_round(
+                    self.numer
+                        .checked_mul(frame_rate.num() as _)
+                        .ok_or(SubtitleError::Overflow)?,
+                    self.denom
+                        .checked_mul(frame_rate.den() as _)
+                        .ok_or(SubtitleError::Overflow)?,
+                    policy.rounding,
+                );
+                if (i64::MAX as u128) < frame.unsigned_abs() {
+                    return Err(SubtitleError::Overflow);
+                }
+                frame_start(frame, frame_rate)
+            }
+            None => div_round(
+                self.numer
+                    .checked_mul(TICKS_PER_SECOND as _)
+                    .ok_or(SubtitleError::Overflow)?,
+                self.denom,
+                policy.rounding,
+            ),
+        };
+
+        i64::try_from(ticks)
+            .map(Tick)
+            .map_err(|_| SubtitleError::Overflow)
+    }
+}
+
+/// Parse `HH:MM:SS` where minutes and seconds have exactly two digits and
+/// hours at least two.
+fn clock(
+    hours: &str,
+    minutes: &str,
+    seconds: &str,
+) -> Result<Seconds, SubtitleError> {
+    if hours.len() < 2 || 2 != minutes.len() || 2 != seconds.len() {
+        return Err(SubtitleError::Syntax);
+    }
+
+    let (hours, minutes, seconds) =
+        (digits(hours)?, digits(minutes)?, digits(seconds)?);
+    if 60 <= minutes || 60 <= seconds {
+        return Err(SubtitleError::OutOfRange);
+    }
+
+    Ok(Seconds::new(
+        (hours as i128 * 60 + minutes as i128) * 60 + seconds as i128,
+        1,
+    ))
+}
+
+/// Parse `HH:MM:SS` plus exactly three digits of milliseconds.
+fn clock_millis(
+    hours: &str,
+    minutes: &str,
+    seconds: &str,
+    millis: &str,
+) -> Result<Seconds, SubtitleError> {
+    if 3 != millis.len() {
+        return Err(SubtitleError::Syntax);
+    }
+
+    clock(hours, minutes, seconds)?
+        .add(Seconds::new(digits(millis)? as _, 1000))
+}
+
+/// Parse `integer.fraction` exactly.
+fn parse_decimal(
+    integer: &str,
+    fraction: &str,
+) -> Result<Seconds, SubtitleError> {
+    if 18 < fraction.len() {
+        return Err(SubtitleError::Overflow);
+    }
+
+    let integer = digits(integer)?;
+    let (fraction, scale) = if fraction.is_empty() {
+        (0, 1)
+    } else {
+        (digits(fraction)?, 10i128.pow(fraction.len() as _))
+    };
+
+    Ok(Seconds::new(
+        integer as i128 * scale + fraction as i128,
+        scale,
+    ))
+}
+
+#[inline]
+fn digits(s: &str) -> Result<u64, SubtitleError> {
+    parse_digits(s).ok_or(SubtitleError::Syntax)
+}
@@ -1,18 +1,22 @@
+extern crate std;
+
 use super::*;
+use proptest::prelude::Strategy;
 #[cfg(feature = "std")]
 use std::time::Duration;
+use std::{string::ToString, vec::Vec};
 
 #[test]
 #[cfg(feature = "std")]
 fn test_from_duration() {
     let duration = Duration::from_secs_f64(2.5);
-    let tick = Tick::from(duration);
+    let tick = Tick::try_from(duration).unwrap();
 
     // 2.5 seconds * 3,603,600 ticks/second = 9,009,000 ticks.
     assert_eq!(tick.0, (2.5 * TICKS_PER_SECOND as f64) as i64);
 
     // Verify round-trip conversion.
-    let back_to_duration = Duration::from(tick);
+    let back_to_duration = Duration::try_from(tick).unwrap();
     assert!((back_to_duration.as_secs_f64() - 2.5).abs() < 1e-10);
 }
 
@@ -20,7 +24,7 @@ fn test_from_duration() {
 #[cfg(feature = "std")]
 fn test_duration_zero() {
     let duration = Duration::ZERO;
-    let tick = Tick::from(duration);
+    let tick = Tick::try_from(duration).unwrap();
     assert_eq!(tick.0, 0);
 }
 
@@ -30,12 +34,1863 @@ fn test_subsecond_precision() {
     // Test with a duration that has nanosecond precision.
     //  1.5 seconds.
     let duration = Duration::new(1, 500_000_000);
-    let tick = Tick::from(duration);
+    let tick = Tick::try_from(duration).unwrap();
 
     // 1.5 seconds * 3,603,600 ticks/second = 5,405,400 ticks,
     assert_eq!(tick.0, (1.5 * TICKS_PER_SECOND as f64) as i64);
 }
 
+#[test]
+#[cfg(feature = "std")]
+fn test_duration_exact() {
+    // A third of a second used to lose precision going through `f64`.
+    let third = Duration::new(0, 333_333_333);
+    assert_eq!(Tick::try_from(third), Ok(Tick::new(TICKS_PER_SECOND / 3)));
+    assert_eq!(
+        Duration::try_from(Tick::new(TICKS_PER_SECOND / 3)),
+        Ok(third)
+    );
+    assert_eq!(
+        Tick::from_duration(third, Rounding::Floor),
+        Ok(Tick::new(TICKS_PER_SECOND / 3 - 1))
+    );
+
+    // Very long durations.
+    let long = Duration::new(1 << 40, 500_000_000);
+    let tick = Tick::try_from(long).unwrap();
+    assert_eq!(Duration::try_from(tick), Ok(long));
+    assert_eq!(Tick::try_from(Duration::MAX), Err(DurationError::Overflow));
+
+    assert_eq!(
+        Duration::try_from(Tick::new(-1)),
+        Err(DurationError::Negative)
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_instant() {
+    let epoch = std::time::Instant::now();
+    let later = epoch + Duration::from_millis(1500);
+
+    let tick = Tick::from_instant(later, epoch, Rounding::Nearest).unwrap();
+    assert_eq!(tick, Tick::from_secs(1.5));
+    assert_eq!(tick.to_instant(epoch, Rounding::Nearest), Ok(later));
+    assert_eq!(
+        Tick::from_instant(epoch, later, Rounding::Nearest),
+        Ok(Tick::from_secs(-1.5))
+    );
+    assert_eq!(
+        Tick::from_secs(-1.5).to_instant(later, Rounding::Nearest),
+        Ok(epoch)
+    );
+}
+
+#[test]
+fn test_operators() {
+    let a = Tick::new(7);
+    let b = Tick::new(3);
+    // Exercise the reference variants, as used when operating on borrowed
+    // ticks, e.g. from `Iterator::iter()`.
+    let (ref_a, ref_b) = (&a, &b);
+
+    assert_eq!(Tick::new(10), a + b);
+    assert_eq!(Tick::new(10), ref_a + b);
+    assert_eq!(Tick::new(4), a - ref_b);
+    assert_eq!(Tick::new(-4), ref_b - ref_a);
+    assert_eq!(Tick::new(-7), -a);
+    assert_eq!(Tick::new(-7), -ref_a);
+    assert_eq!(Tick::new(1), a % b);
+    assert_eq!(Tick::new(-1), -a % b);
+    assert_eq!(Tick::new(14), ref_a * 2u8);
+    assert_eq!(Tick::new(4), a / 2.0f64);
+    assert_eq!(2.5, Tick::new(5) / Tick::new(2));
+    assert_eq!(0.5, ref_a / Tick::new(14));
+
+    let mut tick = a;
+    tick += b;
+    tick -= ref_b;
+    tick += Tick::new(2);
+    assert_eq!(Tick::new(9), tick);
+    tick *= 4i32;
+    tick /= 3u64;
+    assert_eq!(Tick::new(12), tick);
+    tick %= Tick::new(5);
+    assert_eq!(Tick::new(2), tick);
+    tick *= 1.5f32;
+    tick /= 0.5f64;
+    assert_eq!(Tick::new(6), tick);
+
+    assert_eq!(Tick::new(10), [a, b].into_iter().sum());
+    assert_eq!(Tick::new(10), [a, b].iter().sum());
+    assert_eq!(Tick::new(0), core::iter::empty::<Tick>().sum());
+}
+
+#[test]
+fn test_frame_rate_normalization() {
+    use core::hash::BuildHasher;
+    use std::{collections::hash_map::RandomState, format};
+
+    let fps_48_2 = FrameRate::new(48, 2).unwrap();
+    assert_eq!(FrameRate::FILM, fps_48_2);
+    assert_eq!((24, 1), (fps_48_2.num(), fps_48_2.den()));
+    assert_eq!(None, FrameRate::new(0, 1));
+    assert_eq!(None, FrameRate::new(1, 0));
+
+    let state = RandomState::new();
+    assert_eq!(state.hash_one(FrameRate::FILM), state.hash_one(fps_48_2));
+
+    // Ordered by the actual rate.
+    let mut rates = [
+        FrameRate::FPS_60,
+        FrameRate::NTSC_FILM,
+        FrameRate::PAL,
+        FrameRate::FILM,
+        FrameRate::NTSC,
+    ];
+    rates.sort();
+    assert_eq!(
+        [
+            FrameRate::NTSC_FILM,
+            FrameRate::FILM,
+            FrameRate::PAL,
+            FrameRate::NTSC,
+            FrameRate::FPS_60,
+        ],
+        rates
+    );
+
+    // Arithmetic.
+    assert_eq!(FrameRate::NTSC_HIGH, FrameRate::NTSC * 2);
+    assert_eq!(FrameRate::PAL, FrameRate::PAL_HIGH / 2);
+    assert_eq!(FrameRate::new(15000, 1001), Some(FrameRate::NTSC / 2));
+    assert_eq!(None, FrameRate::FILM.checked_div(0));
+    assert_eq!(None, FrameRate::FILM.checked_mul(u32::MAX));
+    assert_eq!(None, FrameRate::FILM.checked_mul(0));
+
+    // Display.
+    assert_eq!("24", FrameRate::FILM.to_string());
+    assert_eq!("23.976 (24000/1001)", FrameRate::NTSC_FILM.to_string());
+    assert_eq!("29.97 (30000/1001)", FrameRate::NTSC.to_string());
+    assert_eq!("12.5 (25/2)", format!("{}", FrameRate::PAL / 2));
+    assert_eq!("0.333 (1/3)", FrameRate::new(1, 3).unwrap().to_string());
+
+    // FromStr.
+    let parse = |s: &str| s.parse::<FrameRate>();
+    assert_eq!(Ok(FrameRate::FILM), parse("24"));
+    assert_eq!(Ok(FrameRate::FILM), parse("24p"));
+    assert_eq!(Ok(FrameRate::FILM), parse("24.000"));
+    assert_eq!(Ok(FrameRate::FILM), parse("48/2"));
+    assert_eq!(Ok(FrameRate::NTSC), parse("29.97"));
+    assert_eq!(Ok(FrameRate::NTSC), parse("30000/1001"));
+    assert_eq!(Ok(FrameRate::NTSC), parse("29.97 (30000/1001)"));
+    assert_eq!(Ok(FrameRate::NTSC), parse("59.94i"));
+    assert_eq!(Ok(FrameRate::PAL), parse("50i"));
+    assert_eq!(Ok(FrameRate::NTSC_FILM), parse("23.976"));
+    assert_eq!(Ok(FrameRate::NTSC_FILM), parse("23.98"));
+    assert_eq!(Ok(FrameRate::NTSC_HIGH), parse("59.94"));
+    assert_eq!(
+        FrameRate::new(25, 2).ok_or(FrameRateError::Zero),
+        parse("12.5")
+    );
+    assert_eq!(
+        FrameRate::new(1, 2).ok_or(FrameRateError::Zero),
+        parse("0.5")
+    );
+    assert_eq!(Err(FrameRateError::Zero), parse("0"));
+    assert_eq!(Err(FrameRateError::Zero), parse("24/0"));
+    assert_eq!(Err(FrameRateError::Overflow), parse("5000000000"));
+    for s in ["", "24.", ".5", "2x4", "24/", "/1001", "-24", "24pi"] {
+        assert_eq!(Err(FrameRateError::Syntax), parse(s), "{s}");
+    }
+    for frame_rate in [
+        FrameRate::FILM,
+        FrameRate::NTSC,
+        FrameRate::NTSC_FILM,
+        FrameRate::NTSC_HIGH,
+        FrameRate::PAL / 2,
+    ] {
+        assert_eq!(Ok(frame_rate), parse(&frame_rate.to_string()));
+    }
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_frame_rate_serde() {
+    let frame_rate: FrameRate =
+        serde_json::from_str(r#"{"num":48,"den":2}"#).unwrap();
+    assert_eq!((24, 1), (frame_rate.num(), frame_rate.den()));
+    assert_eq!("24", frame_rate.to_string());
+    assert_eq!(
+        r#"{"num":30000,"den":1001}"#,
+        serde_json::to_string(&FrameRate::NTSC).unwrap()
+    );
+    assert!(serde_json::from_str::<FrameRate>(r#"{"num":0,"den":1}"#).is_err());
+}
+
+#[test]
+#[cfg(all(feature = "rkyv-bytecheck", feature = "std"))]
+fn test_frame_rate_rkyv() {
+    let unreduced = FrameRate {
+        num: NonZeroU32::new(48).unwrap(),
+        den: NonZeroU32::new(2).unwrap(),
+    };
+    let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&unreduced).unwrap();
+    let frame_rate =
+        rkyv::from_bytes::<FrameRate, rkyv::rancor::Error>(&bytes).unwrap();
+    assert_eq!((24, 1), (frame_rate.num(), frame_rate.den()));
+}
+
+#[test]
+fn test_frame_rate_snapped() {
+    let snap = FrameRate::from_f64_snapped;
+
+    for (rate, frame_rate) in [
+        (23.976, FrameRate::NTSC_FILM),
+        (23.98, FrameRate::NTSC_FILM),
+        (29.97, FrameRate::NTSC),
+        (59.94, FrameRate::NTSC_HIGH),
+        (24.0, FrameRate::FILM),
+        (25.0, FrameRate::PAL),
+        (30000.0 / 1001.0, FrameRate::NTSC),
+        (119.88, FrameRate::new(120000, 1001).unwrap()),
+        (47.952, FrameRate::new(48000, 1001).unwrap()),
+    ] {
+        assert_eq!(Some(frame_rate), snap(rate, 0.005), "{rate}");
+    }
+
+    // The closest standard rate wins.
+    assert_eq!(Some(FrameRate::FPS_30), snap(29.99, 0.05));
+    assert_eq!(Some(FrameRate::NTSC), snap(29.975, 0.05));
+
+    // Best rational approximations.
+    assert_eq!(FrameRate::new(25, 2), snap(12.5, 0.0));
+    assert_eq!(FrameRate::new(59, 2), snap(29.5, 0.0));
+    // Without tolerance, 29.97 is not NTSC.
+    assert_eq!(FrameRate::new(2997, 100), snap(29.97, 0.0));
+    assert_eq!(FrameRate::new(1, 3), snap(1.0 / 3.0, 1e-9));
+    assert_eq!(FrameRate::new(355, 113), snap(core::f64::consts::PI, 1e-6));
+    assert_eq!(FrameRate::new(2, 1001), snap(2.0 / 1001.0, 1e-12));
+
+    // Nothing close enough or invalid.
+    assert_eq!(None, snap(core::f64::consts::PI, 0.0));
+    assert_eq!(None, snap(0.0, 1.0));
+    assert_eq!(None, snap(-24.0, 1.0));
+    assert_eq!(None, snap(f64::NAN, 1.0));
+    assert_eq!(None, snap(f64::INFINITY, 1.0));
+    assert_eq!(None, snap(24.0, -1.0));
+}
+
+#[test]
+#[cfg(feature = "float_frame_rate")]
+fn test_frame_rate_f64() {
+    let fps = FramesPerSecF64::from(FrameRate::NTSC);
+    assert_eq!(30000.0 / 1001.0, fps.get());
+    assert_eq!(Ok(FrameRate::NTSC), FrameRate::try_from(fps));
+    assert_eq!(
+        Ok(FrameRate::FILM),
+        FrameRate::try_from(FramesPerSecF64::new(24.0).unwrap())
+    );
+    assert_eq!(
+        Err(FrameRateError::Inexact),
+        FrameRate::try_from(
+            FramesPerSecF64::new(core::f64::consts::PI).unwrap()
+        )
+    );
+}
+
+#[test]
+fn test_catalog() {
+    assert!(catalog::iter().is_sorted_by_key(|entry| entry.frame_rate()));
+    for (index, entry) in catalog::iter().enumerate() {
+        // Names are unique and parse to the frame rate they stand for.
+        assert_eq!(Some(entry), catalog::by_name(entry.name()));
+        assert_eq!(Ok(entry.frame_rate()), entry.name().parse());
+        assert_eq!(Some(entry), catalog::by_frame_rate(entry.frame_rate()));
+        assert!(
+            catalog::CATALOG[..index]
+                .iter()
+                .all(|other| other.frame_rate() < entry.frame_rate())
+        );
+        assert_eq!(entry.is_exact(), entry.frame_rate().is_exact());
+    }
+
+    let drop_frame = catalog::iter()
+        .filter(|entry| entry.is_drop_frame_eligible())
+        .map(|entry| entry.frame_rate())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        [FrameRate::NTSC, FrameRate::NTSC_HIGH, FrameRate::FPS_119_88],
+        drop_frame[..]
+    );
+
+    let interlaced = catalog::iter()
+        .filter(|entry| entry.is_interlaced_capable())
+        .map(|entry| entry.frame_rate())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        [FrameRate::PAL, FrameRate::NTSC, FrameRate::FPS_30],
+        interlaced[..]
+    );
+
+    let fps_165 = catalog::by_name("165").unwrap();
+    assert_eq!("Gaming displays", fps_165.description());
+    assert_eq!(TICKS_PER_SECOND % 165 == 0, fps_165.is_exact());
+    // 96 fps needs a tick count divisible by 32.
+    assert!(!catalog::by_name("96").unwrap().is_exact());
+    assert_eq!(
+        FrameRate::NTSC_FILM,
+        catalog::by_name("23.976").unwrap().frame_rate()
+    );
+    assert_eq!(None, catalog::by_name("23.98"));
+    assert_eq!(
+        None,
+        catalog::by_frame_rate(FrameRate::from_int(7).unwrap())
+    );
+}
+
+#[test]
+fn test_resolution() {
+    use resolution::{ResolutionError, TickResolution};
+
+    const DISPLAYS: TickResolution = match TickResolution::for_rates(
+        &[FrameRate::FPS_144, FrameRate::FPS_240, FrameRate::FILM],
+        &[],
+    ) {
+        Ok(resolution) => resolution,
+        Err(_) => panic!(),
+    };
+    const _: () = assert!(DISPLAYS.is_covered());
+    assert_eq!(720, DISPLAYS.ticks_per_second());
+
+    // Agrees with `FrameRate::is_exact()`.
+    for entry in catalog::iter() {
+        assert_eq!(
+            entry.frame_rate().is_exact(),
+            TickResolution::for_rates(&[entry.frame_rate()], &[])
+                .unwrap()
+                .is_covered(),
+            "{}",
+            entry.name()
+        );
+    }
+
+    // Audio sample rates need a much higher resolution.
+    let audio = TickResolution::for_rates(&[], &[44_100, 48_000]).unwrap();
+    assert_eq!(7_056_000, audio.ticks_per_second());
+    assert!(!audio.is_covered());
+    let both =
+        TickResolution::for_rates(&[FrameRate::NTSC], &[48_000]).unwrap();
+    assert!(
+        audio.is_covered_by(
+            TickResolution::for_rates(&[FrameRate::PAL], &[44_100, 48_000])
+                .unwrap()
+        )
+    );
+    assert_eq!(240_000, both.ticks_per_second());
+    assert!(both.headroom_years() > 1_000_000);
+
+    assert_eq!(
+        (i64::MAX / TICKS_PER_SECOND / 31_557_600) as u64,
+        TickResolution::CURRENT.headroom_years()
+    );
+    assert!(TickResolution::CURRENT.is_covered());
+    assert_eq!(
+        Ok(TickResolution::for_rates(&[], &[1]).unwrap()),
+        TickResolution::for_rates(&[], &[])
+    );
+
+    assert_eq!(
+        Err(ResolutionError::ZeroSampleRate),
+        TickResolution::for_rates(&[], &[48_000, 0])
+    );
+    assert_eq!(
+        Err(ResolutionError::Overflow),
+        TickResolution::for_rates(&[], &[u32::MAX, u32::MAX - 1, u32::MAX - 2])
+    );
+}
+
+#[test]
+fn test_typed_frame() {
+    use frame::{Film, Frame, Ntsc, NtscFilm, Pal, Rate};
+
+    let frame = Frame::<Film>::new(24);
+    assert_eq!(Tick::from_secs_int(1), frame.to_tick());
+    assert_eq!(FrameRate::FILM, frame.frame_rate());
+    assert_eq!(frame, Frame::from_tick(Tick::from_secs_int(1)));
+    assert_eq!(frame, Frame::from_tick(Tick::from_secs(1.04)));
+    assert_eq!(
+        Frame::new(23),
+        Frame::<Film>::from_tick(Tick::new(-1) + frame.to_tick())
+    );
+
+    // Same-rate arithmetic.
+    let mut sum = frame + Frame::new(6) - 2;
+    assert_eq!(28, sum.index());
+    sum -= Frame::new(4);
+    sum += 1;
+    assert_eq!(Frame::new(25), sum);
+    assert_eq!(Frame::new(-25), -sum);
+
+    // Explicit rate conversion.
+    assert_eq!(Frame::<Pal>::new(25), frame.to_rate(Pal, Rounding::Floor));
+    let frame = Frame::<Film>::new(1);
+    assert_eq!(1, frame.to_rate(Pal, Rounding::Floor).index());
+    assert_eq!(2, frame.to_rate(Pal, Rounding::Ceil).index());
+    // 24 fps frame 1000 starts at 41.666s, NTSC frame 1248.75.
+    let frame = Frame::<Film>::new(1000);
+    assert_eq!(1248, frame.to_rate(Ntsc, Rounding::Floor).index());
+    assert_eq!(
+        Frame::<NtscFilm>::new(1000),
+        Frame::<NtscFilm>::new(1000)
+            .to_rate(Ntsc, Rounding::Nearest)
+            .to_rate(NtscFilm, Rounding::Nearest)
+    );
+
+    // The frame containing a tick and its first tick agree across rates.
+    for tick in [-TICKS_PER_SECOND, -1, 0, 1, 12345678].map(Tick::new) {
+        let frame = Frame::<Ntsc>::from_tick(tick);
+        assert!(frame.to_tick() <= tick);
+        assert!(tick < (frame + 1).to_tick());
+        assert_eq!(frame.index(), tick.to_frames(FrameRate::NTSC),);
+        assert_eq!(
+            frame.to_tick(),
+            Tick::from_frames(frame.index(), Ntsc.frame_rate()),
+        );
+    }
+
+    // Runtime rates.
+    let frame = Frame::with_rate(50, FrameRate::PAL);
+    assert_eq!(Tick::from_secs_int(2), Tick::from(frame));
+    assert_eq!(
+        Frame::with_rate(51, FrameRate::PAL),
+        frame + Frame::with_rate(1, FrameRate::PAL)
+    );
+    assert_eq!(Frame::<Pal>::new(50), frame.to_rate(Pal, Rounding::Nearest));
+}
+
+#[test]
+#[should_panic(expected = "different rates")]
+fn test_typed_frame_rate_mismatch() {
+    let _ = frame::Frame::with_rate(1, FrameRate::PAL)
+        + frame::Frame::with_rate(1, FrameRate::FILM);
+}
+
+#[test]
+fn test_frame_position() {
+    use frame::FramePosition;
+
+    let tick = Tick::from_frames_f64(12.5, FrameRate::FILM);
+    assert_eq!(Tick::new(TICKS_PER_SECOND * 25 / 48), tick);
+    assert_eq!(12.5, tick.to_frames_f64(FrameRate::FILM));
+    assert_eq!(
+        FramePosition {
+            frame: 12,
+            offset: Tick::new(TICKS_PER_SECOND / 48),
+        },
+        FramePosition::from_tick(tick, FrameRate::FILM)
+    );
+
+    // A quarter frame at 24 fps is not an integral number of ticks, so this
+    // is the next tick.
+    let tick = Tick::from_frames_f64(12.25, FrameRate::FILM);
+    assert_eq!(
+        Tick::new((TICKS_PER_SECOND as u64 * 49).div_ceil(96) as _),
+        tick
+    );
+    assert_eq!(
+        Tick::new((TICKS_PER_SECOND as u64).div_ceil(96) as _),
+        FramePosition::from_tick(tick, FrameRate::FILM).offset
+    );
+    assert_eq!(
+        -0.5,
+        Tick::from_frames_f64(-0.5, FrameRate::FILM)
+            .to_frames_f64(FrameRate::FILM)
+    );
+
+    // Whole frames are their first tick, also at NTSC rates.
+    for frame in [-1001, -1, 0, 1, 29, 30, 1001, 107892] {
+        assert_eq!(
+            Tick::from_frames(frame, FrameRate::NTSC),
+            Tick::from_frames_f64(frame as f64, FrameRate::NTSC)
+        );
+    }
+
+    // Fractional frames round trip to within a tick.
+    for frames in [0.1, 0.5, 1.75, 29.97, 1000.001, -3.3] {
+        let tick = Tick::from_frames_f64(frames, FrameRate::NTSC);
+        let frame_length =
+            1.0 / 120240.12 * (3_603_600 / TICKS_PER_SECOND) as f64;
+        let round_trip = tick.to_frames_f64(FrameRate::NTSC);
+        assert!(frames <= round_trip, "{frames}");
+        assert!(round_trip - frames < frame_length * 1.01, "{frames}");
+    }
+
+    // Normalization.
+    let position = FramePosition {
+        frame: 1,
+        offset: Tick::new(-1),
+    };
+    assert_eq!(
+        FramePosition {
+            frame: 0,
+            offset: Tick::from_frames(1, FrameRate::PAL) - Tick::new(1),
+        },
+        position.normalize(FrameRate::PAL)
+    );
+
+    assert_eq!(
+        Tick::new(i64::MAX),
+        Tick::from_frames_f64(f64::INFINITY, FrameRate::FILM)
+    );
+    assert_eq!(
+        Tick::new(i64::MIN),
+        Tick::from_frames_f64(-1e300, FrameRate::FILM)
+    );
+    assert_eq!(
+        Tick::new(0),
+        Tick::from_frames_f64(f64::NAN, FrameRate::FILM)
+    );
+}
+
+proptest::proptest! {
+    #[test]
+    fn prop_frame_position(
+        tick in -(1i64 << 50)..(1i64 << 50),
+        rate in proptest::sample::select(
+            catalog::CATALOG.iter().map(|entry| entry.frame_rate()).collect::<Vec<_>>()
+        ),
+    ) {
+        let tick = Tick::new(tick);
+        let position = frame::FramePosition::from_tick(tick, rate);
+
+        proptest::prop_assert_eq!(tick, position.to_tick(rate));
+        proptest::prop_assert!(Tick::new(0) <= position.offset);
+        proptest::prop_assert!(
+            tick < Tick::from_frames(position.frame + 1, rate)
+        );
+        proptest::prop_assert_eq!(
+            position.frame,
+            tick.to_frames_f64(rate).floor() as i64
+        );
+    }
+}
+
+#[test]
+fn test_frames_tile_timeline() {
+    use frame::{
+        frame_containing, frame_duration, frame_end, frame_range, frame_start,
+    };
+
+    for entry in catalog::iter() {
+        let rate = entry.frame_rate();
+        // The exact frame length, as a fraction of ticks.
+        let numer = TICKS_PER_SECOND as i128 * rate.den() as i128;
+        let denom = rate.num() as i128;
+
+        for first in [-2 * rate.num() as i64, 0, 1 << 30] {
+            let mut expected_start = frame_start(first, rate);
+            for frame in first..first + 2 * rate.num() as i64 {
+                let range = frame_range(frame, rate);
+
+                // No gap and no overlap with the previous frame.
+                assert_eq!(expected_start, range.start);
+                assert_eq!(frame_end(frame, rate), range.end);
+                expected_start = range.end;
+
+                // Every frame holds the floor or ceiling of the exact
+                // frame length in ticks, and never nothing.
+                let duration = frame_duration(frame, rate);
+                assert!(Tick::new(0) < duration);
+                assert!(
+                    numer / denom == duration.0 as i128
+                        || (numer as u128).div_ceil(denom as u128)
+                            == duration.0 as u128
+                );
+                if rate.is_exact() {
+                    assert_eq!((numer / denom) as i64, duration.0);
+                }
+
+                // The frame's first and last tick belong to it; the ticks
+                // around it do not.
+                assert_eq!(frame, frame_containing(range.start, rate));
+                assert_eq!(
+                    frame,
+                    frame_containing(range.end - Tick::new(1), rate)
+                );
+                assert_eq!(
+                    frame - 1,
+                    frame_containing(range.start - Tick::new(1), rate)
+                );
+                assert_eq!(frame + 1, frame_containing(range.end, rate));
+            }
+        }
+    }
+
+    // Frames starting exactly on a tick start there, the others on the next
+    // tick.
+    assert_eq!(Tick::new(0), frame_start(0, FrameRate::NTSC));
+    assert_eq!(
+        Tick::new((TICKS_PER_SECOND as u64 * 1001).div_ceil(30000) as _),
+        frame_start(1, FrameRate::NTSC)
+    );
+    assert_eq!(
+        Tick::new(TICKS_PER_SECOND * 1001),
+        frame_start(30000, FrameRate::NTSC)
+    );
+}
+
+proptest::proptest! {
+    #[test]
+    fn prop_frame_containing(
+        tick in proptest::num::i64::ANY.prop_map(|tick| tick >> 2),
+        rate in proptest::sample::select(
+            catalog::CATALOG.iter().map(|entry| entry.frame_rate()).collect::<Vec<_>>()
+        ),
+    ) {
+        let tick = Tick::new(tick);
+        let frame = frame::frame_containing(tick, rate);
+
+        proptest::prop_assert!(frame::frame_range(frame, rate).contains(&tick));
+    }
+}
+
+#[test]
+fn test_quantize() {
+    use core::num::NonZeroU32;
+    use quantize::Grid;
+
+    let nonzero = |n| NonZeroU32::new(n).unwrap();
+
+    // Steps, with ties away from the origin.
+    let grid = Grid::step(Tick::new(10)).unwrap().with_origin(Tick::new(3));
+    assert_eq!(Tick::new(3), grid.origin());
+    let snap = |tick, rounding| Tick::new(tick).quantize(grid, rounding).0;
+    assert_eq!(13, snap(17, Rounding::Floor));
+    assert_eq!(23, snap(17, Rounding::Ceil));
+    assert_eq!(13, snap(17, Rounding::Nearest));
+    assert_eq!(23, snap(18, Rounding::Nearest));
+    assert_eq!(-7, snap(-2, Rounding::Nearest));
+    assert_eq!(3, snap(-2, Rounding::TowardZero));
+    assert_eq!(13, snap(22, Rounding::TowardZero));
+    assert_eq!(13, snap(13, Rounding::Ceil));
+    assert_eq!(None, Grid::step(Tick::new(0)));
+    assert_eq!(None, Grid::step(Tick::new(-1)));
+
+    // Every 4th frame at 24 fps are sixths of a second.
+    let grid = Grid::frames(FrameRate::FILM).every(nonzero(4));
+    assert_eq!(
+        Tick::new(TICKS_PER_SECOND / 3),
+        Tick::new(TICKS_PER_SECOND / 4).quantize(grid, Rounding::Nearest)
+    );
+    assert_eq!(
+        Grid::frames(FrameRate::FPS_30).every(nonzero(5)).point(7),
+        { Tick::new(TICKS_PER_SECOND * 7 / 6) }
+    );
+
+    // 128 bpm beats are not an integral number of ticks apart.
+    let grid = Grid::beats(nonzero(128));
+    assert_eq!(
+        Tick::new((60 * TICKS_PER_SECOND as u64 * 3).div_ceil(128) as _),
+        grid.point(3)
+    );
+    assert_eq!(3, grid.index(grid.point(3), Rounding::Floor));
+    assert_eq!(2, grid.index(grid.point(3) - Tick::new(1), Rounding::Floor));
+    assert_eq!(
+        Tick::from_secs_int(60),
+        Tick::from_secs(59.9).quantize(grid, Rounding::Ceil)
+    );
+
+    // Points less than a tick apart saturate the index.
+    for grid in [
+        Grid::frames(FrameRate::new(u32::MAX, 1).unwrap()),
+        Grid::beats(nonzero(u32::MAX)),
+    ] {
+        assert_eq!(i64::MAX, grid.index(Tick::new(i64::MAX), Rounding::Floor));
+        assert_eq!(i64::MIN, grid.index(Tick::new(i64::MIN), Rounding::Ceil));
+        assert!(
+            Tick::new(0) < Tick::new(i64::MAX).quantize(grid, Rounding::Floor)
+        );
+    }
+}
+
+proptest::proptest! {
+    #[test]
+    fn prop_quantize(
+        tick in -(1i64 << 50)..(1i64 << 50),
+        origin in -(1i64 << 40)..(1i64 << 40),
+        every in 1u32..100,
+        rate in proptest::sample::select(
+            catalog::CATALOG.iter().map(|entry| entry.frame_rate()).collect::<Vec<_>>()
+        ),
+    ) {
+        let tick = Tick::new(tick);
+        let grid = quantize::Grid::frames(rate);
+
+        // Snapping to frames gives the first tick of a frame.
+        proptest::prop_assert_eq!(
+            frame::frame_start(frame::frame_containing(tick, rate), rate),
+            tick.quantize(grid, Rounding::Floor)
+        );
+
+        let grid = grid
+            .every(core::num::NonZeroU32::new(every).unwrap())
+            .with_origin(Tick::new(origin));
+        let floor = tick.quantize(grid, Rounding::Floor);
+        let ceil = tick.quantize(grid, Rounding::Ceil);
+        let nearest = tick.quantize(grid, Rounding::Nearest);
+
+        proptest::prop_assert!(floor <= tick && tick <= ceil);
+        proptest::prop_assert!(
+            floor == ceil
+                || grid.index(floor, Rounding::Floor) + 1
+                    == grid.index(ceil, Rounding::Floor)
+        );
+        proptest::prop_assert!(nearest == floor || nearest == ceil);
+        proptest::prop_assert!(
+            (nearest - tick).abs() <= (floor - tick).abs()
+                && (nearest - tick).abs() <= (ceil - tick).abs()
+        );
+        for rounding in [
+            Rounding::Floor,
+            Rounding::Ceil,
+            Rounding::Nearest,
+            Rounding::TowardZero,
+        ] {
+            let snapped = tick.quantize(grid, rounding);
+            proptest::prop_assert_eq!(snapped, snapped.quantize(grid, rounding));
+            proptest::prop_assert_eq!(snapped, snapped.quantize(grid, Rounding::Floor));
+            proptest::prop_assert_eq!(snapped, snapped.quantize(grid, Rounding::Ceil));
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_interval() {
+    use interval::{IntervalMap, IntervalSet};
+
+    let t = Tick::new;
+    let set = |ranges: &[(i64, i64)]| {
+        ranges
+            .iter()
+            .map(|&(start, end)| t(start)..t(end))
+            .collect::<IntervalSet>()
+    };
+
+    let a = set(&[(0, 10), (20, 30), (10, 15), (40, 40)]);
+    assert_eq!([t(0)..t(15), t(20)..t(30)], a.ranges());
+    assert!(a.contains(t(14)) && !a.contains(t(15)) && !a.contains(t(-1)));
+    assert!(a.contains_range(t(20)..t(30)) && !a.contains_range(t(14)..t(21)));
+    assert!(a.intersects(t(14)..t(21)) && !a.intersects(t(15)..t(20)));
+
+    let b = set(&[(5, 25)]);
+    assert_eq!(set(&[(0, 30)]), a.union(&b));
+    assert_eq!(set(&[(0, 5), (25, 30)]), a.difference(&b));
+    assert_eq!(set(&[(5, 15), (20, 25)]), a.intersection(&b));
+    assert_eq!(
+        set(&[(-5, 0), (15, 20), (30, 35)]),
+        a.complement(t(-5)..t(35))
+    );
+
+    // Frames are missing if any of their ticks are.
+    let mut cache = IntervalSet::new();
+    cache.insert_frames(0..10, FrameRate::NTSC);
+    let frame_start = |frame| frame::frame_start(frame, FrameRate::NTSC);
+    cache.remove(frame_start(3) + t(1)..frame_start(4));
+    assert_eq!(
+        [3..4, 10..12],
+        cache.missing_frames(0..12, FrameRate::NTSC)[..]
+    );
+    assert!(cache.missing_frames(4..10, FrameRate::NTSC).is_empty());
+
+    let mut map = IntervalMap::new();
+    map.insert(t(0)..t(10), 'a');
+    map.insert(t(20)..t(30), 'a');
+    map.insert(t(10)..t(20), 'a');
+    assert_eq!(1, map.len());
+    map.insert(t(5)..t(15), 'b');
+    map.remove(t(25)..t(27));
+    assert_eq!(
+        [
+            (t(0)..t(5), &'a'),
+            (t(5)..t(15), &'b'),
+            (t(15)..t(25), &'a'),
+            (t(27)..t(30), &'a')
+        ],
+        map.iter().collect::<Vec<_>>()[..]
+    );
+    assert_eq!(Some(&'b'), map.get(t(5)));
+    assert_eq!(None, map.get(t(25)));
+    assert_eq!(
+        [(t(12)..t(15), &'b'), (t(15)..t(25), &'a')],
+        map.overlapping(t(12)..t(26)).collect::<Vec<_>>()[..]
+    );
+    assert_eq!(set(&[(0, 25), (27, 30)]), map.to_set());
+}
+
+proptest::proptest! {
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn prop_interval(
+        ops in proptest::collection::vec((0i64..64, 0i64..64, 0u8..3), 0..32),
+        other in proptest::collection::vec((0i64..64, 0i64..64), 0..8),
+    ) {
+        use interval::{IntervalMap, IntervalSet};
+
+        // Compare against one flag or value per tick.
+        let mut set = IntervalSet::new();
+        let mut flags = [false; 64];
+        let mut map = IntervalMap::new();
+        let mut values = [None; 64];
+        for &(start, end, value) in &ops {
+            let range = Tick::new(start)..Tick::new(end);
+            if value == 0 {
+                set.remove(range.clone());
+                map.remove(range);
+            } else {
+                set.insert(range.clone());
+                map.insert(range, value);
+            }
+            for tick in start.max(0)..end {
+                flags[tick as usize] = value != 0;
+                values[tick as usize] = (value != 0).then_some(value);
+            }
+        }
+        let other = other
+            .iter()
+            .map(|&(start, end)| Tick::new(start)..Tick::new(end))
+            .collect::<IntervalSet>();
+        let union = set.union(&other);
+        let difference = set.difference(&other);
+        let intersection = set.intersection(&other);
+        let complement = set.complement(Tick::new(8)..Tick::new(56));
+        for tick in 0..64 {
+            let (flag, t) = (flags[tick as usize], Tick::new(tick));
+            proptest::prop_assert_eq!(flag, set.contains(t));
+            proptest::prop_assert_eq!(values[tick as usize].as_ref(), map.get(t));
+            proptest::prop_assert_eq!(flag || other.contains(t), union.contains(t));
+            proptest::prop_assert_eq!(flag && !other.contains(t), difference.contains(t));
+            proptest::prop_assert_eq!(flag && other.contains(t), intersection.contains(t));
+            proptest::prop_assert_eq!(
+                !flag && (8..56).contains(&tick),
+                complement.contains(t)
+            );
+        }
+
+        // Ranges are sorted, not empty and merged.
+        for set in [&set, &union, &difference, &intersection, &complement] {
+            for pair in set.ranges().windows(2) {
+                proptest::prop_assert!(pair[0].end < pair[1].start);
+            }
+            proptest::prop_assert!(set.ranges().iter().all(|r| !r.is_empty()));
+        }
+        let entries = map.iter().collect::<Vec<_>>();
+        for pair in entries.windows(2) {
+            proptest::prop_assert!(pair[0].0.end <= pair[1].0.start);
+            proptest::prop_assert!(
+                pair[0].0.end < pair[1].0.start || pair[0].1 != pair[1].1
+            );
+        }
+        proptest::prop_assert_eq!(set, map.to_set());
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_scheduler() {
+    use scheduler::{Scheduler, TimerWheel};
+
+    let t = Tick::new;
+    let mut scheduler = Scheduler::new();
+    let a = scheduler.schedule(t(10), 'a');
+    let b = scheduler.schedule(t(10), 'b');
+    let c = scheduler.schedule(t(20), 'c');
+    scheduler.schedule(t(-5), 'd');
+    assert_eq!(4, scheduler.len());
+    assert_eq!(Some((t(-5), &'d')), scheduler.first());
+    assert_eq!(Some((t(20), &'c')), scheduler.last());
+
+    assert_eq!(Some('b'), scheduler.cancel(b));
+    assert_eq!(None, scheduler.cancel(b));
+    assert!(scheduler.reschedule(c, t(10)));
+    assert_eq!(Some(t(10)), scheduler.due_at(c));
+    assert_eq!(Some(&'c'), scheduler.get(c));
+
+    let due = scheduler.pop_due(t(0)).unwrap();
+    assert_eq!((t(-5), 'd'), (due.at, due.event));
+    assert_eq!(None, scheduler.pop_due(t(9)));
+    assert_eq!(
+        [(a, 'a'), (c, 'c')],
+        scheduler
+            .due(t(10))
+            .map(|due| (due.id, due.event))
+            .collect::<Vec<_>>()[..]
+    );
+    assert!(scheduler.is_empty());
+    assert!(!scheduler.reschedule(a, t(0)));
+
+    // Backward, equal ticks pop in reverse order.
+    for (at, event) in [(0, 'a'), (5, 'b'), (5, 'c'), (9, 'd')] {
+        scheduler.schedule(t(at), event);
+    }
+    assert_eq!(
+        ['d', 'c', 'b'],
+        scheduler
+            .due_rev(t(5))
+            .map(|due| due.event)
+            .collect::<Vec<_>>()[..]
+    );
+
+    assert!(TimerWheel::<()>::new(t(0), t(0)).is_none());
+    let mut wheel = TimerWheel::new(t(7), t(100)).unwrap();
+    let a = wheel.schedule(t(1 << 40), 'a');
+    wheel.schedule(t(101), 'b');
+    wheel.schedule(t(50), 'c');
+    let d = wheel.schedule(t(101), 'd');
+    wheel.schedule(t(100), 'e');
+    assert_eq!(Some('d'), wheel.cancel(d));
+    assert_eq!(
+        ['c', 'e', 'b'],
+        wheel
+            .due(t(1 << 39))
+            .map(|due| due.event)
+            .collect::<Vec<_>>()[..]
+    );
+    assert!(wheel.reschedule(a, t(-1)));
+    assert_eq!(Some(t(-1)), wheel.due_at(a));
+    assert_eq!('a', wheel.pop_due(t(0)).unwrap().event);
+    assert!(wheel.is_empty());
+}
+
+proptest::proptest! {
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn prop_timer_wheel(
+        granularity in 1i64..1000,
+        start in -(1i64 << 40)..(1i64 << 40),
+        ops in proptest::collection::vec(
+            (0u8..5, -(1i64 << 41)..(1i64 << 41), 0usize..64),
+            0..128,
+        ),
+    ) {
+        use scheduler::{Scheduler, TimerWheel};
+
+        // The wheel pops exactly what a scheduler pops.
+        let mut scheduler = Scheduler::new();
+        let mut wheel = TimerWheel::new(Tick::new(granularity), Tick::new(start))
+            .unwrap();
+        let mut ids = Vec::new();
+        let mut until = Tick::new(start);
+        for (index, &(op, at, pick)) in ops.iter().enumerate() {
+            let at = Tick::new(at);
+            match op {
+                0 | 1 => {
+                    let id = scheduler.schedule(at, index);
+                    proptest::prop_assert_eq!(id, wheel.schedule(at, index));
+                    ids.push(id);
+                }
+                2 if !ids.is_empty() => {
+                    let id = ids[pick % ids.len()];
+                    proptest::prop_assert_eq!(scheduler.cancel(id), wheel.cancel(id));
+                }
+                3 if !ids.is_empty() => {
+                    let id = ids[pick % ids.len()];
+                    proptest::prop_assert_eq!(
+                        scheduler.reschedule(id, at),
+                        wheel.reschedule(id, at)
+                    );
+                }
+                _ => {
+                    until = until.max(at);
+                    proptest::prop_assert_eq!(
+                        scheduler.due(until).collect::<Vec<_>>(),
+                        wheel.due(until).collect::<Vec<_>>()
+                    );
+                }
+            }
+            proptest::prop_assert_eq!(scheduler.len(), wheel.len());
+        }
+        let end = Tick::new(i64::MAX);
+        proptest::prop_assert_eq!(
+            scheduler.due(end).collect::<Vec<_>>(),
+            wheel.due(end).collect::<Vec<_>>()
+        );
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_timeline() {
+    use timeline::{
+        Clip, ClipAt, Item, Layer, Timeline, TimelineError, Track, Transition,
+    };
+
+    let t = Tick::new;
+    let clip = |media, start, duration| {
+        Item::Clip(Clip::new(media, Tick::new(start), Tick::new(duration)))
+    };
+
+    let mut track = Track::new();
+    track.push(clip("a", 0, 40)).unwrap();
+    track.overwrite(t(10), clip("b", 100, 10)).unwrap();
+    assert_eq!(
+        [clip("a", 0, 10), clip("b", 100, 10), clip("a", 20, 20)],
+        track.items()
+    );
+    assert_eq!(Some(t(20)..t(40)), track.record_range(2));
+
+    // Edits past the end pad with a gap.
+    track.insert(t(60), clip("c", 0, 10)).unwrap();
+    assert_eq!(t(70), track.duration());
+    track.lift(t(10)..t(20)).unwrap();
+    assert_eq!(
+        [
+            clip("a", 0, 10),
+            Item::Gap(t(10)),
+            clip("a", 20, 20),
+            Item::Gap(t(20)),
+            clip("c", 0, 10)
+        ],
+        track.items()
+    );
+
+    track.ripple_delete(t(5)..t(25)).unwrap();
+    track.ripple_delete(t(45)..t(100)).unwrap();
+    assert_eq!(
+        [
+            clip("a", 0, 5),
+            clip("a", 25, 15),
+            Item::Gap(t(20)),
+            clip("c", 0, 5)
+        ],
+        track.items()
+    );
+
+    track.roll(1, t(2)).unwrap();
+    track.slip(3, t(3)).unwrap();
+    track.slide(1, t(-2)).unwrap();
+    assert_eq!(
+        [
+            clip("a", 0, 5),
+            clip("a", 27, 13),
+            Item::Gap(t(22)),
+            clip("c", 3, 5)
+        ],
+        track.items()
+    );
+    assert_eq!(t(45), track.duration());
+
+    assert_eq!(Err(TimelineError::NotAClip), track.slip(2, t(1)));
+    assert_eq!(Err(TimelineError::NoNeighbor), track.roll(0, t(1)));
+    assert_eq!(Err(TimelineError::NoNeighbor), track.slide(3, t(1)));
+    assert_eq!(Err(TimelineError::Index), track.roll(9, t(1)));
+    assert_eq!(Err(TimelineError::Duration), track.roll(1, t(13)));
+    assert_eq!(Err(TimelineError::Duration), track.push(Item::Gap(t(0))));
+    assert_eq!(
+        Err(TimelineError::Negative),
+        track.insert(t(-1), Item::Gap(t(1)))
+    );
+    assert_eq!(Ok(Item::Gap(t(22))), track.remove(2));
+    assert_eq!(Err(TimelineError::Index), track.remove(3));
+
+    // Transitions.
+    let mut track = Track::new();
+    track.push(clip("a", 0, 10)).unwrap();
+    track.push(clip("b", 100, 10)).unwrap();
+    let transition = Transition {
+        in_offset: t(2),
+        out_offset: t(3),
+    };
+    track.set_transition(1, Some(transition)).unwrap();
+    assert_eq!(
+        Err(TimelineError::TransitionTooLong),
+        track.set_transition(
+            1,
+            Some(Transition {
+                in_offset: t(11),
+                out_offset: t(0)
+            })
+        )
+    );
+
+    let at = |index: usize, source_time| ClipAt {
+        index,
+        clip: track.items()[index].as_clip().unwrap(),
+        source_time: t(source_time),
+    };
+    assert_eq!(Some(Layer::Clip(at(0, 7))), track.at(t(7)));
+    assert_eq!(
+        Some(Layer::Transition {
+            from: Some(at(0, 8)),
+            to: Some(at(1, 98)),
+            elapsed: t(0),
+            duration: t(5),
+        }),
+        track.at(t(8))
+    );
+    assert_eq!(
+        Some(Layer::Transition {
+            from: Some(at(0, 12)),
+            to: Some(at(1, 102)),
+            elapsed: t(4),
+            duration: t(5),
+        }),
+        track.at(t(12))
+    );
+    assert_eq!(Some(Layer::Clip(at(1, 103))), track.at(t(13)));
+    assert_eq!(None, track.at(t(20)));
+    assert_eq!(None, track.at(t(-1)));
+
+    // Edits shorten transitions to fit into their neighbors.
+    let transition_of =
+        |track: &Track<&str>, index: usize| -> Option<Transition> {
+            track.items()[index].as_clip()?.transition
+        };
+    let mut rolled = track.clone();
+    rolled.roll(1, t(-9)).unwrap();
+    assert_eq!(
+        Some(Transition {
+            in_offset: t(1),
+            out_offset: t(3),
+        }),
+        transition_of(&rolled, 1)
+    );
+    let mut deleted = track.clone();
+    deleted.ripple_delete(t(1)..t(10)).unwrap();
+    assert_eq!(
+        Some(Transition {
+            in_offset: t(1),
+            out_offset: t(3),
+        }),
+        transition_of(&deleted, 1)
+    );
+    let mut overwritten = track.clone();
+    overwritten.overwrite(t(12), clip("c", 0, 8)).unwrap();
+    assert_eq!(
+        Some(Transition {
+            in_offset: t(2),
+            out_offset: t(2),
+        }),
+        transition_of(&overwritten, 1)
+    );
+    let mut removed = track.clone();
+    removed.remove(0).unwrap();
+    assert_eq!(
+        Some(Transition {
+            in_offset: t(0),
+            out_offset: t(3),
+        }),
+        transition_of(&removed, 0)
+    );
+
+    // Tracks stack from bottom to top.
+    let second = Tick::from_secs_int(1);
+    let mut timeline = Timeline::new(FrameRate::FILM);
+    let mut bottom = Track::new();
+    bottom
+        .push(Item::Clip(Clip::new("a", t(0), second)))
+        .unwrap();
+    let mut top = Track::new();
+    top.insert(second / 2, Item::Clip(Clip::new("x", t(0), second)))
+        .unwrap();
+    timeline.push_track(bottom);
+    assert_eq!(1, timeline.push_track(top));
+    assert_eq!(second * 3 / 2, timeline.duration());
+    assert_eq!(
+        Some(0),
+        timeline.visible_at_frame(11).map(|(track, _)| track)
+    );
+    assert_eq!(
+        Some(1),
+        timeline.visible_at_frame(12).map(|(track, _)| track)
+    );
+    assert_eq!(2, timeline.layers_at(second * 3 / 4).count());
+    assert_eq!(1, timeline.layers_at(second).count());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_vfr() {
+    use frame::frame_start;
+    use vfr::{VfrError, VfrTimeline};
+
+    let t = Tick::new;
+    assert_eq!(
+        Err(VfrError::NotIncreasing { frame: 2 }),
+        VfrTimeline::new([t(0), t(10), t(10)].into())
+    );
+
+    let timeline =
+        VfrTimeline::new([t(0), t(10), t(30), t(35)].into()).unwrap();
+    assert_eq!(None, timeline.frame_at(t(-1)));
+    assert_eq!(Some(0), timeline.frame_at(t(9)));
+    assert_eq!(Some(1), timeline.frame_at(t(10)));
+    assert_eq!(Some(3), timeline.frame_at(t(1000)));
+    assert_eq!(Some(0), timeline.nearest_frame(t(-5)));
+    assert_eq!(Some(1), timeline.nearest_frame(t(19)));
+    assert_eq!(Some(2), timeline.nearest_frame(t(20)));
+    assert_eq!(Some(3), timeline.nearest_frame(t(1000)));
+    assert_eq!(None, VfrTimeline::default().nearest_frame(t(0)));
+    assert_eq!(Some(t(30)), timeline.frame_end(1));
+    assert_eq!(None, timeline.frame_end(3));
+    assert_eq!(t(35), timeline.duration());
+
+    // v2 round trips any ticks.
+    let v2 = timeline.to_v2();
+    assert!(v2.starts_with("# timecode format v2\n0\n"));
+    assert_eq!(Ok(&timeline), VfrTimeline::from_v2(&v2).as_ref());
+    assert_eq!(
+        Ok(&timeline),
+        VfrTimeline::from_v2(&v2.replace("format v2\n", "format v2\n\n# x\n"))
+            .as_ref()
+    );
+    assert_eq!(
+        Err(VfrError::Header),
+        VfrTimeline::from_v2("# timecode format v1\n0\n")
+    );
+    assert_eq!(
+        Err(VfrError::Syntax { line: 3 }),
+        VfrTimeline::from_v2("# timestamp format v2\n0\n4x\n")
+    );
+    let v2 = VfrTimeline::from_v2("# timecode format v2\n0\n1000\n1500.5\n");
+    assert_eq!(
+        // 1.5005 seconds, to the nearest tick.
+        Some((TICKS_PER_SECOND * 3001 + 1000) / 2000),
+        v2.unwrap().frame_start(2).map(|tick| tick.0)
+    );
+
+    // Constant rates become a single range.
+    let ntsc = VfrTimeline::from_frame_rate(1000, FrameRate::NTSC);
+    // With `low_res`, 29.97 spans the same ticks.
+    let average = ntsc.average_frame_rate().unwrap();
+    assert!((average.to_f64() - FrameRate::NTSC.to_f64()).abs() < 1e-3);
+    assert_eq!(Some(FrameRate::NTSC), ntsc.nearest_standard_rate());
+    let v1 = ntsc.to_v1().unwrap();
+    assert_eq!("# timecode format v1\nAssume 29.97\n0,999,29.97\n", v1);
+    assert_eq!(Ok(&ntsc), VfrTimeline::from_v1(&v1, 1000).as_ref());
+
+    // Mixed rates become a range each.
+    let mut timestamps = ntsc.timestamps()[..100].to_vec();
+    let start = frame_start(100, FrameRate::NTSC);
+    timestamps.extend(
+        (0..50).map(|frame| start + frame_start(frame, FrameRate::FILM)),
+    );
+    let mixed = VfrTimeline::new(timestamps).unwrap();
+    let v1 = mixed.to_v1().unwrap();
+    assert_eq!(
+        "# timecode format v1\nAssume 24\n0,99,29.97\n100,149,24\n",
+        v1
+    );
+    assert_eq!(Ok(&mixed), VfrTimeline::from_v1(&v1, 150).as_ref());
+
+    // Frames outside the ranges use the assumed rate.
+    let v1 =
+        VfrTimeline::from_v1("# timecode format v1\nassume 25\n2,3,50\n", 6)
+            .unwrap();
+    let frame = frame_start(1, FrameRate::PAL).0;
+    assert_eq!(
+        [0, frame, 2 * frame, 5 * frame / 2, 3 * frame, 4 * frame],
+        v1.timestamps()
+            .iter()
+            .map(|tick| tick.0)
+            .collect::<Vec<_>>()[..]
+    );
+    assert_eq!(
+        Err(VfrError::Syntax { line: 4 }),
+        VfrTimeline::from_v1(
+            "# timecode format v1\nAssume 25\n0,9,30\n5,6,24\n",
+            20
+        )
+    );
+    assert_eq!(
+        Err(VfrError::Syntax { line: 2 }),
+        VfrTimeline::from_v1("# timecode format v1\n0,9,30\n", 20)
+    );
+
+    // Huge frame counts fail instead of allocating.
+    assert_eq!(
+        Err(VfrError::Overflow),
+        VfrTimeline::from_v1("# timecode format v1\nAssume 24\n", usize::MAX)
+    );
+    assert_eq!(
+        Err(VfrError::Capacity),
+        VfrTimeline::from_v1(
+            "# timecode format v1\nAssume 4000000000\n",
+            usize::MAX / 4
+        )
+    );
+
+    assert_eq!(
+        Err(VfrError::Offset),
+        VfrTimeline::new([t(1), t(2)].into()).unwrap().to_v1()
+    );
+    assert_eq!(None, VfrTimeline::default().average_frame_rate());
+}
+
+proptest::proptest! {
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn prop_vfr(
+        start in -(1i64 << 40)..(1i64 << 40),
+        durations in proptest::collection::vec(1i64..1_000_000, 0..64),
+        runs in proptest::collection::vec((0usize..6, 1i64..40), 0..8),
+    ) {
+        use vfr::VfrTimeline;
+
+        // v2 round trips any timestamps.
+        let timestamps = durations
+            .iter()
+            .scan(start, |tick, duration| {
+                *tick += duration;
+                Some(Tick::new(*tick))
+            })
+            .collect();
+        let timeline = VfrTimeline::new(timestamps).unwrap();
+        proptest::prop_assert_eq!(
+            Ok(timeline.clone()),
+            VfrTimeline::from_v2(&timeline.to_v2())
+        );
+
+        // v1 round trips runs of catalog rates.
+        let mut timestamps = Vec::new();
+        let mut start = Tick::new(0);
+        for &(rate, frames) in &runs {
+            let rate = [
+                FrameRate::FILM,
+                FrameRate::NTSC_FILM,
+                FrameRate::PAL,
+                FrameRate::NTSC,
+                FrameRate::NTSC_HIGH,
+                FrameRate::PAL_HIGH,
+            ][rate];
+            timestamps
+                .extend((0..frames).map(|frame| start + frame::frame_start(frame, rate)));
+            start += frame::frame_start(frames, rate);
+        }
+        let timeline = VfrTimeline::new(timestamps).unwrap();
+        let v1 = timeline.to_v1().unwrap();
+        proptest::prop_assert_eq!(
+            Ok(timeline.clone()),
+            VfrTimeline::from_v1(&v1, timeline.len())
+        );
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn test_cadence() {
+    use cadence::{Cadence, CadenceError, Gap, Jitter};
+    use frame::frame_start;
+
+    let t = Tick::new;
+    assert_eq!(Err(CadenceError::TooShort), Cadence::analyze(&[], 0.1));
+    assert_eq!(
+        Err(CadenceError::TooShort),
+        Cadence::analyze(&[t(5), t(5)], 0.1)
+    );
+    assert_eq!(
+        Err(CadenceError::NotSorted { index: 2 }),
+        Cadence::analyze(&[t(0), t(10), t(5)], 0.1)
+    );
+
+    // Spans wider than a tick or too many frames do not overflow.
+    for timestamps in [
+        [t(0), t(i64::MAX), t(i64::MAX)],
+        [t(i64::MIN), t(i64::MAX), t(i64::MAX)],
+        [t(i64::MIN), t(i64::MIN + 1), t(i64::MAX)],
+        [t(i64::MIN), t(0), t(i64::MAX)],
+    ] {
+        assert_eq!(Err(CadenceError::Rate), Cadence::analyze(&timestamps, 0.1));
+    }
+    let timestamps: Vec<_> = (0..5)
+        .map(|tick| t(i64::MIN + tick))
+        .chain([t(i64::MAX)])
+        .collect();
+    assert_eq!(
+        Err(CadenceError::Overflow),
+        Cadence::analyze(&timestamps, 0.1)
+    );
+    let timestamps = [t(i64::MAX - 2), t(i64::MAX - 1), t(i64::MAX)];
+    assert_eq!(
+        FrameRate::new(TICKS_PER_SECOND as _, 1),
+        Cadence::analyze(&timestamps, 0.1)
+            .ok()
+            .map(|cadence| cadence.frame_rate())
+    );
+
+    // Exact timestamps of each catalog rate give that rate back.
+    for entry in catalog::iter() {
+        let rate = entry.frame_rate();
+        let timestamps: Vec<_> = (0..100)
+            .map(|frame| t(1000) + frame_start(frame, rate))
+            .collect();
+        let cadence = Cadence::analyze(&timestamps, 0.001).unwrap();
+        assert_eq!(rate, cadence.frame_rate(), "{}", entry.name());
+        assert_eq!((0..100).collect::<Vec<_>>(), cadence.frames());
+        assert!(cadence.jitter().max <= t(1));
+        assert!(cadence.gaps().is_empty() && cadence.duplicates().is_empty());
+        assert!(!cadence.is_pulldown());
+    }
+
+    // Drops and repeats do not skew the rate.
+    let mut timestamps: Vec<_> = (0..=200)
+        .filter(|frame| frame % 7 != 3)
+        .map(|frame| frame_start(frame, FrameRate::NTSC))
+        .collect();
+    timestamps.insert(10, timestamps[9]);
+    timestamps.insert(20, timestamps[19]);
+    let cadence = Cadence::analyze(&timestamps, 0.001).unwrap();
+    assert_eq!(FrameRate::NTSC, cadence.frame_rate());
+    assert_eq!([10, 20], cadence.duplicates());
+    assert_eq!(
+        Gap {
+            index: 3,
+            missing: 1
+        },
+        cadence.gaps()[0]
+    );
+    assert_eq!(
+        (0..200).filter(|frame| frame % 7 == 3).count(),
+        cadence.gaps().len()
+    );
+    assert_eq!(Some(&200), cadence.frames().last());
+
+    // Jitter of alternately one tick early and late.
+    let frame = TICKS_PER_SECOND / 25;
+    let timestamps: Vec<_> = (0..100)
+        .map(|index| t(index * frame + if 0 == index % 2 { -1 } else { 1 }))
+        .collect();
+    let cadence = Cadence::analyze(&timestamps, 0.1).unwrap();
+    assert_eq!(FrameRate::PAL, cadence.frame_rate());
+    assert_eq!(t(0), cadence.grid().origin());
+    assert_eq!(
+        Jitter {
+            mean: t(1),
+            rms: t(1),
+            max: t(1),
+        },
+        cadence.jitter()
+    );
+
+    // Film on 59.94 fields, two and three fields per frame.
+    let field = |field| frame_start(field, FrameRate::NTSC_HIGH);
+    let timestamps: Vec<_> = (0..48)
+        .map(|frame| field(frame / 2 * 5 + frame % 2 * 2))
+        .collect();
+    let cadence = Cadence::analyze(&timestamps, 0.01).unwrap();
+    assert_eq!(FrameRate::NTSC_FILM, cadence.frame_rate());
+    assert!(cadence.is_pulldown());
+    assert!(cadence.gaps().is_empty() && cadence.duplicates().is_empty());
+
+    // Without a catalog rate in reach, the fit is kept.
+    let timestamps: Vec<_> = (0..50)
+        .map(|frame| frame_start(frame, FrameRate::new(20, 1).unwrap()))
+        .collect();
+    assert_eq!(
+        FrameRate::new(20, 1),
+        Cadence::analyze(&timestamps, 0.1)
+            .map(|cadence| cadence.frame_rate())
+            .ok()
+    );
+}
+
+proptest::proptest! {
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn prop_cadence(
+        entry in 0..catalog::CATALOG.len(),
+        origin in -(1i64 << 40)..(1i64 << 40),
+        frames in proptest::collection::vec((0u8..20, -20i64..=20), 100..300),
+    ) {
+        use cadence::Cadence;
+
+        // Up to every fourth frame is dropped, frames are repeated, and off
+        // by up to a fiftieth.
+        let rate = catalog::CATALOG[entry].frame_rate();
+        let mut numbers = Vec::new();
+        let mut timestamps = Vec::new();
+        for (number, &(kind, jitter)) in (0..).zip(&frames) {
+            let tick = Tick::new(origin) + frame::frame_start(number, rate);
+            let jitter = frame::frame_start(1, rate) * jitter / 1000;
+            let count = match kind {
+                0 if number % 4 == 1 && number + 1 != frames.len() as i64 => 0,
+                1 => 2,
+                _ => 1,
+            };
+            for _ in 0..count {
+                numbers.push(number);
+                timestamps.push(tick + jitter);
+            }
+        }
+
+        // Less than half the distance to the 1000/1001 rate.
+        let tolerance = rate.to_f64() / 2500.0;
+        let cadence = Cadence::analyze(&timestamps, tolerance).unwrap();
+        proptest::prop_assert_eq!(rate, cadence.frame_rate());
+        let first = cadence.frames()[0];
+        proptest::prop_assert_eq!(
+            numbers,
+            cadence.frames().iter().map(|frame| frame - first).collect::<Vec<_>>()
+        );
+        proptest::prop_assert!(
+            cadence.jitter().max <= frame::frame_start(1, rate) / 25
+        );
+    }
+}
+
+#[test]
+fn test_ruler() {
+    use ruler::{Label, Mark, Ruler, Scale, Viewport};
+
+    let second = Tick::from_secs_int(1);
+    let majors = |ruler: Ruler, viewport: &Viewport| {
+        ruler
+            .marks(viewport)
+            .filter(|mark| mark.major)
+            .collect::<Vec<_>>()
+    };
+
+    // 100 frames at 10 pixels per frame.
+    let viewport = Viewport::new(
+        Tick::new(0)..frame::frame_start(100, FrameRate::FILM),
+        1000.0,
+    )
+    .unwrap();
+    let ruler = Ruler::new(Scale::Frames, FrameRate::FILM);
+    assert_eq!(100, ruler.marks(&viewport).len());
+    assert_eq!(100, ruler.marks(&viewport).count());
+    let marks = majors(ruler, &viewport);
+    assert_eq!(10, marks.len());
+    assert_eq!(
+        Mark {
+            tick: frame::frame_start(10, FrameRate::FILM),
+            pixel: 100.0,
+            major: true,
+            label: Some(Label::Frame(10)),
+        },
+        marks[1]
+    );
+
+    // A second at a pixel per millisecond.
+    let viewport = Viewport::new(Tick::new(0)..second, 1000.0).unwrap();
+    let ruler = Ruler::new(Scale::Seconds, FrameRate::FILM);
+    assert_eq!(100, ruler.marks(&viewport).len());
+    let labels = majors(ruler, &viewport)
+        .iter()
+        .map(|mark| mark.label.unwrap().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(["0.0s", "0.1s", "0.2s"], labels[..3]);
+
+    // Half a second per beat at 100 pixels per second.
+    let viewport = Viewport::new(Tick::new(0)..second * 8, 800.0).unwrap();
+    let ruler = Ruler::new(
+        Scale::Beats(NonZeroU32::new(120).unwrap()),
+        FrameRate::FILM,
+    );
+    assert_eq!(64, ruler.marks(&viewport).len());
+    let marks = majors(ruler, &viewport);
+    assert_eq!(Some(Label::Beat(2)), marks[1].label);
+    assert_eq!(second, marks[1].tick);
+
+    // Negative ticks have no timecode.
+    let viewport = Viewport::new(-second * 2..second * 2, 400.0).unwrap();
+    let ruler =
+        Ruler::new(Scale::Timecode { drop_frame: true }, FrameRate::NTSC)
+            .with_spacing(100.0, 10.0);
+    let marks = majors(ruler, &viewport);
+    assert_eq!(None, marks[0].label);
+    assert_eq!(
+        "00:00:01;00",
+        marks.last().unwrap().label.unwrap().to_string()
+    );
+    assert_eq!(
+        Tick::from_frames(30, FrameRate::NTSC),
+        marks.last().unwrap().tick
+    );
+
+    // Huge ranges do not overflow.
+    let viewport =
+        Viewport::new(Tick::new(i64::MIN / 2)..Tick::new(i64::MAX / 2), 100.0)
+            .unwrap();
+    for scale in [Scale::Frames, Scale::Seconds, Scale::Beats(NonZeroU32::MIN)]
+    {
+        assert!(
+            Ruler::new(scale, FrameRate::NTSC).marks(&viewport).len() < 100
+        );
+    }
+
+    // Extreme rates and tempos are counted exactly.
+    let viewport = Viewport::new(Tick::new(0)..second, 1000.0).unwrap();
+    let ruler = Ruler::new(Scale::Beats(NonZeroU32::MAX), FrameRate::FILM);
+    let marks = majors(ruler, &viewport);
+    assert_eq!(Some(Label::Beat(1 << 23)), marks[1].label);
+    let viewport = Viewport::new(Tick::new(0)..second * 10, 1000.0).unwrap();
+    let ruler = Ruler::new(
+        Scale::Timecode { drop_frame: false },
+        FrameRate::new(u32::MAX, 1).unwrap(),
+    );
+    assert_eq!(second, majors(ruler, &viewport)[1].tick);
+
+    // Zooming keeps the anchor, panning moves by pixels.
+    assert_eq!(None, Viewport::new(second..second, 100.0));
+    assert_eq!(None, Viewport::new(Tick::new(0)..second, 0.0));
+    let mut viewport =
+        Viewport::new(Tick::new(0)..second * 10, 1000.0).unwrap();
+    let anchor = viewport.to_tick(250.0);
+    viewport.zoom(10.0, 250.0);
+    assert_eq!(250.0, viewport.to_pixel(anchor));
+    assert_eq!(second, viewport.visible().end - viewport.visible().start);
+    viewport.pan(-1000.0);
+    assert_eq!(second * 5 / 4, anchor - viewport.visible().start);
+    viewport.zoom(1e30, 0.0);
+    assert_eq!(
+        Tick::new(1),
+        viewport.visible().end - viewport.visible().start
+    );
+    viewport.zoom(f64::NAN, 0.0);
+    assert_eq!(
+        Tick::new(1),
+        viewport.visible().end - viewport.visible().start
+    );
+}
+
+#[test]
+fn test_const() {
+    const FPS_24: FramesPerSec = FramesPerSec::new(24).unwrap();
+    const TWO_SECONDS: Tick = Tick::from_frames_at_fps(48, FPS_24);
+    const NTSC_FRAME: Tick = Tick::from_frames_at_rate(1, FrameRate::NTSC);
+    const ONE_HOUR: Tick = Tick::from_timecode(1, 0, 0, 0, FrameRate::PAL);
+    const TIMECODE: (i64, i64, i64, i64) =
+        Tick::from_secs_int(3661).to_timecode(FrameRate::FILM);
+    const FPS_12: FrameRate = FrameRate::from_int(12).unwrap();
+    const _: () = assert!(FPS_12.is_exact());
+    const _: () = assert!(FrameRate::new(0, 1).is_none());
+
+    assert_eq!(Tick::from_secs_int(2), TWO_SECONDS);
+    assert_eq!(TWO_SECONDS, Tick::from_frames(48, FPS_24));
+    assert_eq!(Tick::from_secs_int(3600), ONE_HOUR);
+    assert_eq!((1, 1, 1, 0), TIMECODE);
+
+    // The first tick of a frame at an inexact rate lies inside the frame.
+    assert!(!FrameRate::NTSC.is_exact());
+    assert!(!FrameRate::NTSC_FILM.is_exact());
+    assert_eq!(
+        Tick::new((TICKS_PER_SECOND as u64 * 1001).div_ceil(30000) as _),
+        NTSC_FRAME
+    );
+    assert_eq!(
+        Tick::new(-TICKS_PER_SECOND * 1001 / 30000),
+        Tick::from_frames_at_rate(-1, FrameRate::NTSC)
+    );
+}
+
+#[test]
+fn test_euclid() {
+    const LOOP: Tick = Tick::new(TICKS_PER_SECOND);
+    const WRAPPED: Tick = Tick::new(-1).rem_euclid(LOOP);
+
+    assert_eq!(Tick::new(TICKS_PER_SECOND - 1), WRAPPED);
+    assert_eq!(-1, Tick::new(-1).div_euclid(LOOP));
+    assert_eq!(0, Tick::new(TICKS_PER_SECOND - 1).div_euclid(LOOP));
+    assert_eq!(2, Tick::new(2 * TICKS_PER_SECOND).div_euclid(LOOP));
+    assert_eq!(
+        Tick::new(0),
+        Tick::new(-2 * TICKS_PER_SECOND).rem_euclid(LOOP)
+    );
+
+    assert_eq!(Tick::new(5), Tick::new(-5).abs());
+    assert_eq!(-1, Tick::new(-5).signum());
+    assert_eq!(0, Tick::new(0).signum());
+    assert_eq!(1, Tick::new(5).signum());
+}
+
+#[test]
+fn test_tick_iter() {
+    let ticks = TickIter::new(Tick::new(-2)..Tick::new(3));
+    assert_eq!(5, ticks.len());
+    assert!(ticks.clone().eq((-2..3).map(Tick::new)));
+    assert!(ticks.clone().rev().eq((-2..3).rev().map(Tick::new)));
+    assert!(
+        TickRevIter::new(Tick::new(-2)..Tick::new(3))
+            .eq((-2..3).rev().map(Tick::new))
+    );
+
+    // Empty and inverted ranges.
+    assert_eq!(0, TickIter::new(Tick::new(3)..Tick::new(3)).len());
+    assert_eq!(None, TickIter::new(Tick::new(3)..Tick::new(-3)).next());
+    assert_eq!(None, TickRevIter::new(Tick::new(3)..Tick::new(-3)).next());
+
+    // The extremes of the range are reachable and fused.
+    let mut ticks = TickIter::new(Tick::new(i64::MIN)..Tick::new(i64::MAX));
+    assert_eq!(Some(Tick::new(i64::MIN)), ticks.next());
+    assert_eq!(Some(Tick::new(i64::MAX - 1)), ticks.next_back());
+    assert_eq!(None, ticks.nth(usize::MAX));
+    assert_eq!(None, ticks.next());
+    assert_eq!(None, ticks.next_back());
+
+    let mut ticks =
+        TickRevIter::new(Tick::new(i64::MIN)..Tick::new(i64::MIN + 2));
+    assert_eq!(Some(Tick::new(i64::MIN + 1)), ticks.next());
+    assert_eq!(Some(Tick::new(i64::MIN)), ticks.next());
+    assert_eq!(None, ticks.next());
+
+    // `last()` does not walk huge ranges.
+    let ticks = TickRevIter::new(Tick::new(i64::MIN)..Tick::new(i64::MAX));
+    assert_eq!(Some(Tick::new(i64::MIN)), ticks.last());
+    let ticks = TickIter::new(Tick::new(i64::MIN)..Tick::new(i64::MAX));
+    assert_eq!(Some(Tick::new(i64::MAX - 1)), ticks.last());
+
+    let mut ticks = Tick::new(i64::MAX - 2).into_iter();
+    assert_eq!(2, ticks.len());
+    assert_eq!(Some(Tick::new(i64::MAX - 1)), ticks.nth(1));
+    assert_eq!(None, ticks.next());
+
+    assert_eq!(
+        Some(Tick::new(i64::MAX)),
+        Tick::new(-1).forward_checked(usize::MAX / 2 + 1)
+    );
+    assert_eq!(None, Tick::new(i64::MAX).forward_checked(1));
+    assert_eq!(None, Tick::new(i64::MIN).backward_checked(1));
+    assert_eq!(Some(5), Tick::steps_between(Tick::new(-2), Tick::new(3)));
+    assert_eq!(None, Tick::steps_between(Tick::new(3), Tick::new(-2)));
+}
+
+proptest::proptest! {
+    #[test]
+    fn prop_tick_iter(
+        start in -1000i64..1000,
+        end in -1000i64..1000,
+        n in 0usize..2100,
+    ) {
+        let range = Tick::new(start)..Tick::new(end);
+        let expected = (start..end).map(Tick::new).collect::<Vec<_>>();
+
+        let ticks = TickIter::new(range.clone());
+        proptest::prop_assert_eq!(expected.len(), ticks.len());
+        proptest::prop_assert!(ticks.clone().eq(expected.iter().copied()));
+        proptest::prop_assert!(
+            ticks.clone().rev().eq(expected.iter().rev().copied())
+        );
+        proptest::prop_assert_eq!(
+            expected.get(n).copied(),
+            ticks.clone().nth(n)
+        );
+        proptest::prop_assert_eq!(
+            expected.iter().rev().nth(n).copied(),
+            ticks.clone().nth_back(n)
+        );
+
+        let ticks = TickRevIter::new(range);
+        proptest::prop_assert_eq!(expected.len(), ticks.len());
+        proptest::prop_assert!(
+            ticks.clone().eq(expected.iter().rev().copied())
+        );
+        proptest::prop_assert!(
+            ticks.clone().rev().eq(expected.iter().copied())
+        );
+        proptest::prop_assert_eq!(
+            expected.iter().rev().nth(n).copied(),
+            ticks.clone().nth(n)
+        );
+        proptest::prop_assert_eq!(
+            expected.get(n).copied(),
+            ticks.clone().nth_back(n)
+        );
+    }
+
+    #[test]
+    fn prop_tick_iter_alternating(
+        start in proptest::num::i64::ANY,
+        len in 0u8..64,
+        steps in proptest::collection::vec(
+            (proptest::bool::ANY, 0usize..8),
+            0..32,
+        ),
+    ) {
+        let end = start.saturating_add(len as _);
+        let expected = (start..end).map(Tick::new).collect::<Vec<_>>();
+        let mut expected = expected.as_slice();
+        let mut ticks = TickIter::new(Tick::new(start)..Tick::new(end));
+
+        for (front, n) in steps {
+            let tick = if front {
+                let tick = expected.get(n).copied();
+                expected = expected.get(n + 1..).unwrap_or_default();
+                proptest::prop_assert_eq!(tick, ticks.nth(n));
+                tick
+            } else {
+                let index = expected.len().checked_sub(n + 1);
+                let tick = index.map(|index| expected[index]);
+                expected = &expected[..index.unwrap_or(0)];
+                proptest::prop_assert_eq!(tick, ticks.nth_back(n));
+                tick
+            };
+            proptest::prop_assert_eq!(expected.len(), ticks.len());
+            if tick.is_none() {
+                proptest::prop_assert_eq!(None, ticks.next());
+            }
+        }
+    }
+}
+
+#[test]
+fn test_frame_iter() {
+    let frames = FrameRate::FILM.frames(Tick::new(0)..Tick::from_secs(1.0));
+    assert_eq!(24, frames.len());
+    assert!(
+        frames
+            .clone()
+            .all(|(frame, tick)| tick
+                == Tick::new(frame * TICKS_PER_SECOND / 24))
+    );
+    assert_eq!(
+        frames
+            .clone()
+            .rev()
+            .map(|(frame, _)| frame)
+            .collect::<Vec<_>>(),
+        (0..24).rev().collect::<Vec<_>>()
+    );
+
+    // Every 6th frame, from either end.
+    let mut steps = frames.clone().step_by(6);
+    assert_eq!(4, steps.len());
+    assert_eq!(Some((6, Tick::from_secs(0.25))), steps.nth(1));
+    let mut frames = frames;
+    assert_eq!(Some((18, Tick::from_secs(0.75))), frames.nth_back(5));
+    assert_eq!(18, frames.len());
+
+    // Fractional rates do not drift: each start is exact for its frame.
+    let hour = 3600 * TICKS_PER_SECOND;
+    let mut frames =
+        FrameRate::NTSC.frames(Tick::new(hour)..Tick::new(hour * 2));
+    assert_eq!(107892, frames.len());
+    let (frame, tick) = frames.next().unwrap();
+    assert_eq!(107893, frame);
+    assert!(Tick::new(hour) <= tick);
+    let containing = |tick: Tick| {
+        div_round(
+            tick.0 as i128 * 30000,
+            TICKS_PER_SECOND as i128 * 1001,
+            Rounding::Floor,
+        ) as i64
+    };
+    assert_eq!(frame, containing(tick));
+    assert!(frames.all(|(frame, tick)| frame == containing(tick)
+        && frame - 1 == containing(tick - Tick::new(1))));
+
+    // Negative and empty ranges.
+    assert_eq!(
+        Some((-1, Tick::new(-TICKS_PER_SECOND / 24))),
+        FrameRate::FILM
+            .frames(Tick::new(-TICKS_PER_SECOND / 24)..Tick::new(1))
+            .next()
+    );
+    assert_eq!(0, FrameRate::FILM.frames(Tick::new(1)..Tick::new(2)).len());
+    assert_eq!(0, FrameRate::FILM.frames(Tick::new(2)..Tick::new(1)).len());
+}
+
 #[test]
 fn test_frame_conversions() {
     // Test 60 FPS,
@@ -47,7 +1902,7 @@ fn test_frame_conversions() {
 
     // 60 frames should be 1 second worth of ticks,
     let sixty_frames = Tick::from_frames(60, fps_60);
-    assert_eq!(sixty_frames.0, TICKS_PER_SECOND as i64);
+    assert_eq!(sixty_frames.0, TICKS_PER_SECOND);
 
     // Test round-trip conversion,
     assert_eq!(sixty_frames.to_frames(fps_60), 60);
@@ -98,7 +1953,7 @@ fn test_frame_edge_cases() {
 
     // Test negative values.
     // -1 second.
-    let negative_ticks = Tick::new(-TICKS_PER_SECOND as i64);
+    let negative_ticks = Tick::new(-TICKS_PER_SECOND);
     assert_eq!(negative_ticks.to_frames(fps_60), -60);
     assert_eq!(Tick::from_frames(-60, fps_60), negative_ticks);
 }
@@ -175,3 +2030,538 @@ fn test_timecode_conversion() {
         assert_eq!((h, m, s, f), (1, 30, 45, 15));
     }
 }
+
+#[test]
+#[cfg(feature = "otio")]
+fn test_otio_rational_time() {
+    use crate::otio::{OtioError, RationalTime};
+
+    // Integer frames at 24 fps round-trip.
+    let tick = Tick::from_frames(37, NonZeroU32::new(24).unwrap());
+    let time = RationalTime::from_tick(tick, FrameRate::FILM).unwrap();
+    assert_eq!(time, RationalTime::new(37.0, 24.0));
+    assert_eq!(Tick::try_from(time), Ok(tick));
+
+    // OTIO writes NTSC rates as the closest `f64`.
+    let time = RationalTime::new(0.0, 30000.0 / 1001.0);
+    assert_eq!(time.frame_rate(), Ok(FrameRate::NTSC));
+
+    // Half a tick can not be represented.
+    let half_tick = RationalTime::new(0.5, TICKS_PER_SECOND as f64);
+    assert_eq!(
+        Tick::try_from(half_tick),
+        Err(OtioError::Inexact {
+            nearest: Tick::new(1)
+        })
+    );
+
+    assert_eq!(
+        Tick::try_from(RationalTime::new(f64::NAN, 24.0)),
+        Err(OtioError::NonFinite)
+    );
+    assert_eq!(
+        Tick::try_from(RationalTime::new(1.0, -24.0)),
+        Err(OtioError::InvalidRate(-24.0))
+    );
+    assert_eq!(
+        RationalTime::new(0.0, 1e10).frame_rate(),
+        Err(OtioError::InvalidRate(1e10))
+    );
+    for frame_rate in [FrameRate::NTSC, FrameRate::NTSC_FILM, FrameRate::PAL] {
+        assert_eq!(
+            RationalTime::new(0.0, frame_rate.to_f64()).frame_rate(),
+            Ok(frame_rate)
+        );
+    }
+
+    // Any tick is exact at the tick rate.
+    let time = RationalTime::from_tick_at_tick_rate(Tick::new(-12345)).unwrap();
+    assert_eq!(time.to_tick(), Ok(Tick::new(-12345)));
+}
+
+#[test]
+#[cfg(feature = "otio")]
+fn test_otio_json() {
+    use crate::otio::{RationalTime, TimeRange};
+
+    let json = r#"{"OTIO_SCHEMA":"TimeRange.1","duration":{"OTIO_SCHEMA":"RationalTime.1","rate":24.0,"value":48.0},"start_time":{"OTIO_SCHEMA":"RationalTime.1","rate":24.0,"value":12.0}}"#;
+    let range: TimeRange = serde_json::from_str(json).unwrap();
+    assert_eq!(range.start_time, RationalTime::new(12.0, 24.0));
+    assert_eq!(serde_json::to_string(&range).unwrap(), json);
+
+    let ticks = range.to_range().unwrap();
+    assert_eq!(ticks.start, Tick::from_secs(0.5));
+    assert_eq!(ticks.end, Tick::from_secs(2.5));
+    assert_eq!(TimeRange::from_range(ticks, FrameRate::FILM), Ok(range));
+
+    // The schema is checked.
+    assert!(
+        serde_json::from_str::<RationalTime>(
+            r#"{"OTIO_SCHEMA":"TimeRange.1","rate":24.0,"value":1.0}"#
+        )
+        .is_err()
+    );
+}
+
+#[test]
+fn test_fcpxml_time() {
+    use crate::fcpxml::{FcpxmlError, FcpxmlTime};
+
+    assert_eq!(Tick::from_fcpxml("10s"), Ok(Tick::from_secs(10.0)));
+    assert_eq!(Tick::from_fcpxml("-1/2s"), Ok(Tick::from_secs(-0.5)));
+    assert_eq!(Tick::from_fcpxml("0s"), Ok(Tick::new(0)));
+
+    for invalid in ["", "s", "10", "1/s", "/2s", "1/-2s", "+1s", "1.5s"] {
+        assert_eq!(
+            invalid.parse::<FcpxmlTime>(),
+            Err(FcpxmlError::Syntax),
+            "{invalid:?}"
+        );
+    }
+    assert_eq!(
+        "1/0s".parse::<FcpxmlTime>(),
+        Err(FcpxmlError::ZeroDenominator)
+    );
+
+    // Printing reduces the fraction.
+    let tick = Tick::from_frames(3, NonZeroU32::new(24).unwrap());
+    assert_eq!(tick.to_fcpxml(None).to_string(), "1/8s");
+    assert_eq!(tick.to_fcpxml(Some(FrameRate::FILM)).to_string(), "3/24s");
+    assert_eq!(Tick::new(0).to_fcpxml(None).to_string(), "0s");
+}
+
+#[test]
+fn test_fcpxml_ntsc_round_trip() {
+    use crate::fcpxml::{FcpxmlError, FcpxmlTime};
+
+    assert_eq!(
+        FrameRate::from_frame_duration("1001/30000s"),
+        Ok(FrameRate::NTSC)
+    );
+    assert_eq!(FrameRate::NTSC.frame_duration().to_string(), "1001/30000s");
+    assert_eq!(
+        FrameRate::from_frame_duration("0/30000s"),
+        Err(FcpxmlError::InvalidFrameDuration)
+    );
+
+    // NTSC frames do not fall onto ticks but the first tick of a frame maps
+    // back to the same string.
+    let time: FcpxmlTime = "3003/30000s".parse().unwrap();
+    assert!(matches!(time.to_tick(), Err(FcpxmlError::Inexact { .. })));
+    for frame in (0..100_000u64).step_by(7) {
+        let start = (frame * 1001 * TICKS_PER_SECOND as u64).div_ceil(30000);
+        let time = Tick::new(start as _).to_fcpxml(Some(FrameRate::NTSC));
+        assert_eq!(time.to_string(), std::format!("{}/30000s", frame * 1001));
+        assert_eq!(time.to_string().parse(), Ok(time));
+
+        // Other ticks fall back to fractions of a second.
+        let tick = Tick::new(start as i64 + 1);
+        assert_eq!(
+            tick.to_fcpxml(Some(FrameRate::NTSC)),
+            FcpxmlTime::from_tick(tick)
+        );
+    }
+}
+
+#[test]
+fn test_drop_frame_timecode() {
+    use crate::timecode::{Timecode, TimecodeError};
+
+    let timecode: Timecode = "00:10:00;00".parse().unwrap();
+    assert_eq!(timecode.to_frame_number(FrameRate::NTSC), Ok(17982));
+    assert_eq!(
+        "00:01:00;00"
+            .parse::<Timecode>()
+            .unwrap()
+            .to_frame_number(FrameRate::NTSC),
+        Err(TimecodeError::OutOfRange)
+    );
+    assert_eq!(
+        Timecode::from_frame_number(0, FrameRate::FILM, true),
+        Err(TimecodeError::DropFrameUnsupported)
+    );
+
+    // Labels round-trip across several ten minute blocks.
+    for fps in [FrameRate::NTSC, FrameRate::NTSC_HIGH] {
+        for frame in (0..200_000).step_by(7) {
+            let timecode =
+                Timecode::from_frame_number(frame, fps, true).unwrap();
+            assert_eq!(timecode.to_frame_number(fps), Ok(frame));
+            assert_eq!(timecode.to_string().parse(), Ok(timecode));
+        }
+    }
+}
+
+#[test]
+fn test_subtitle_srt_webvtt() {
+    use crate::subtitle::{self, RoundingPolicy, SubtitleError};
+
+    let policy = RoundingPolicy::default();
+    let tick = subtitle::parse_srt("01:02:03,500", policy).unwrap();
+    assert_eq!(tick, Tick::from_timecode(1, 2, 3, 12, FrameRate::FILM));
+    assert_eq!(
+        subtitle::format_srt(tick, policy).unwrap().to_string(),
+        "01:02:03,500"
+    );
+    assert_eq!(
+        subtitle::parse_webvtt("02:03.500", policy),
+        Ok(tick - Tick::from_secs(3600.0))
+    );
+    assert_eq!(
+        subtitle::format_webvtt(tick, policy).unwrap().to_string(),
+        "01:02:03.500"
+    );
+
+    // One millisecond is 3603.6 ticks.
+    assert_eq!(
+        subtitle::parse_srt("00:00:00,001", policy),
+        Ok(Tick::new((TICKS_PER_SECOND + 500) / 1000))
+    );
+    assert_eq!(
+        subtitle::parse_srt(
+            "00:00:00,001",
+            RoundingPolicy::new(Rounding::Floor)
+        ),
+        Ok(Tick::new(TICKS_PER_SECOND / 1000))
+    );
+
+    // Snapping to the frame grid.
+    let policy =
+        RoundingPolicy::new(Rounding::Ceil).with_frame_rate(FrameRate::PAL);
+    assert_eq!(
+        subtitle::parse_srt("00:00:00,001", policy),
+        Ok(Tick::from_secs(0.04))
+    );
+
+    for invalid in
+        ["00:00:00.000", "0:00:00,000", "00:00:00,00", "00:60:00,000"]
+    {
+        assert!(subtitle::parse_srt(invalid, policy).is_err(), "{invalid:?}");
+    }
+    assert_eq!(
+        subtitle::format_srt(Tick::new(-1), policy),
+        Err(SubtitleError::Negative)
+    );
+}
+
+#[test]
+fn test_subtitle_ttml_scc() {
+    use crate::subtitle::{self, RoundingPolicy, TtmlStyle, TtmlTimeBase};
+
+    let policy = RoundingPolicy::default();
+    let film =
+        TtmlTimeBase::from_attributes(Some(24), None, None, None).unwrap();
+    assert_eq!(film.tick_rate, 24);
+
+    let tick = Tick::from_secs(1.5);
+    for expression in [
+        "1.5s",
+        "1500ms",
+        "0.025m",
+        "36f",
+        "36t",
+        "00:00:01.5",
+        "00:00:01:12",
+    ] {
+        assert_eq!(
+            subtitle::parse_ttml(expression, film, policy),
+            Ok(tick),
+            "{expression:?}"
+        );
+    }
+    for (style, expected) in [
+        (TtmlStyle::Clock, "00:00:01.500"),
+        (TtmlStyle::ClockFrames, "00:00:01:12"),
+        (TtmlStyle::Frames, "36f"),
+        (TtmlStyle::Ticks, "36t"),
+    ] {
+        assert_eq!(
+            subtitle::format_ttml(tick, film, style, policy)
+                .unwrap()
+                .to_string(),
+            expected
+        );
+    }
+
+    // 30 frames at 29.97 fps are 1.001 seconds.
+    let ntsc =
+        TtmlTimeBase::from_attributes(Some(30), Some((1000, 1001)), None, None)
+            .unwrap();
+    assert_eq!(ntsc.frame_rate, FrameRate::NTSC);
+    assert_eq!(
+        subtitle::parse_ttml("30f", ntsc, policy),
+        Ok(Tick::new((TICKS_PER_SECOND * 1001 + 500) / 1000))
+    );
+
+    // Clock values with frames count real seconds, also at 1000/1001 rates.
+    assert_eq!(
+        subtitle::format_ttml(
+            Tick::new(3600 * TICKS_PER_SECOND),
+            ntsc,
+            TtmlStyle::ClockFrames,
+            policy
+        )
+        .unwrap()
+        .to_string(),
+        "01:00:00:00"
+    );
+    for (frame_rate, base) in
+        [(FrameRate::NTSC, 30), (FrameRate::NTSC_FILM, 24)]
+    {
+        let time_base = TtmlTimeBase::from_attributes(
+            Some(base),
+            Some((1000, 1001)),
+            None,
+            None,
+        )
+        .unwrap();
+        for rounding in [Rounding::Floor, Rounding::Nearest, Rounding::Ceil] {
+            let policy =
+                RoundingPolicy::new(rounding).with_frame_rate(frame_rate);
+            for frame in (0..100_000).step_by(7).chain([107_892, 862_884]) {
+                let tick = Tick::new(frame_start(frame, frame_rate) as _);
+                let clock = subtitle::format_ttml(
+                    tick,
+                    time_base,
+                    TtmlStyle::ClockFrames,
+                    policy,
+                )
+                .unwrap()
+                .to_string();
+                assert_eq!(
+                    subtitle::parse_ttml(&clock, time_base, policy),
+                    Ok(tick),
+                    "{clock} at {frame_rate:?}"
+                );
+            }
+        }
+    }
+
+    // SCC timecodes are drop-frame at 29.97 fps.
+    let tick = subtitle::parse_scc("00:01:00;02", policy).unwrap();
+    assert_eq!(tick, Tick::new(TICKS_PER_SECOND * 6006 / 100));
+    assert_eq!(
+        subtitle::format_scc(tick, policy).unwrap().to_string(),
+        "00:01:00;02"
+    );
+}
+
+#[test]
+fn test_iso8601() {
+    use crate::iso8601::Iso8601Error;
+
+    for (s, secs) in [
+        ("PT0S", 0.0),
+        ("PT1H2M3.5S", 3723.5),
+        ("PT1,5S", 1.5),
+        ("P1DT1S", 86_401.0),
+        ("P1W", 604_800.0),
+        ("-PT0.25S", -0.25),
+        ("PT90M", 5400.0),
+        ("PT0.5H", 1800.0),
+    ] {
+        assert_eq!(Tick::from_iso8601(s), Ok(Tick::from_secs(secs)), "{s:?}");
+    }
+
+    for (s, error) in [
+        ("P", Iso8601Error::Syntax),
+        ("PT", Iso8601Error::Syntax),
+        ("1S", Iso8601Error::Syntax),
+        ("PT1S1M", Iso8601Error::Syntax),
+        ("PT1.5M1S", Iso8601Error::Syntax),
+        ("P1Y", Iso8601Error::Nominal),
+        ("P1M", Iso8601Error::Nominal),
+    ] {
+        assert_eq!(Tick::from_iso8601(s), Err(error), "{s:?}");
+    }
+    assert!(matches!(
+        Tick::from_iso8601("PT0.0000001S"),
+        Err(Iso8601Error::Inexact { .. })
+    ));
+
+    for (secs, s) in [
+        (0.0, "PT0S"),
+        (3723.5, "PT1H2M3.5S"),
+        (3600.0, "PT1H"),
+        (-0.04, "-PT0.04S"),
+    ] {
+        assert_eq!(Tick::from_secs(secs).to_iso8601().unwrap().to_string(), s);
+    }
+
+    // One frame at 24 fps is 0.041666... seconds.
+    assert_eq!(
+        Tick::from_frames(1, NonZeroU32::new(24).unwrap()).to_iso8601(),
+        Err(Iso8601Error::NonTerminating)
+    );
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_serde_with_iso8601() {
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Config {
+        #[serde(with = "crate::with::iso8601")]
+        timeout: Tick,
+    }
+
+    let config = Config {
+        timeout: Tick::from_secs(90.5),
+    };
+    let json = serde_json::to_string(&config).unwrap();
+    assert_eq!(json, r#"{"timeout":"PT1M30.5S"}"#);
+    assert_eq!(serde_json::from_str::<Config>(&json).unwrap(), config);
+
+    assert!(serde_json::from_str::<Config>(r#"{"timeout":"P1Y"}"#).is_err());
+    assert!(
+        serde_json::to_string(&Config {
+            timeout: Tick::from_frames(1, NonZeroU32::new(24).unwrap()),
+        })
+        .is_err()
+    );
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_serde_with() {
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Shot {
+        #[serde(with = "crate::with::frame_rate")]
+        frame_rate: FrameRate,
+        #[serde(with = "crate::with::timecode::film")]
+        start: Tick,
+        #[serde(with = "crate::with::frames::film")]
+        frame: Tick,
+        #[serde(with = "crate::with::secs")]
+        secs: Tick,
+        #[serde(with = "crate::with::human")]
+        handles: Tick,
+    }
+
+    let shot = Shot {
+        frame_rate: FrameRate::NTSC,
+        start: Tick::from_timecode(1, 0, 0, 12, FrameRate::FILM),
+        frame: Tick::from_secs(2.0),
+        secs: Tick::from_secs(1.5),
+        handles: Tick::from_secs(3723.5),
+    };
+    let json = serde_json::to_string(&shot).unwrap();
+    assert_eq!(
+        json,
+        r#"{"frame_rate":"30000/1001","start":"01:00:00:12","frame":48,"secs":1.5,"handles":"1h 2m 3.5s"}"#
+    );
+    assert_eq!(serde_json::from_str::<Shot>(&json).unwrap(), shot);
+    assert_eq!(
+        serde_json::from_str::<Shot>(&json.replace("30000/1001", "29.97"))
+            .unwrap(),
+        shot
+    );
+
+    for (field, value) in [
+        ("frame_rate", r#""30000/0""#),
+        ("frame_rate", r#""0""#),
+        ("frame_rate", r#""29.97x""#),
+        ("start", r#""01:00:00:24""#),
+        ("start", r#""01:00:00;12""#),
+        ("secs", "1e300"),
+        ("handles", r#""1x""#),
+        ("handles", r#""""#),
+    ] {
+        let json = json.replace(
+            &serde_json::to_value(&shot).unwrap()[field].to_string(),
+            value,
+        );
+        assert!(serde_json::from_str::<Shot>(&json).is_err(), "{json}");
+    }
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn test_serde_with_human() {
+    use std::format;
+
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Human(#[serde(with = "crate::with::human")] Tick);
+
+    let one_frame = Tick::from_frames(1, NonZeroU32::new(24).unwrap());
+    for (tick, s) in [
+        (Tick::new(0), "0s"),
+        (Tick::from_secs(-60.0), "-1m"),
+        (Tick::from_secs(7200.25), "2h 0.25s"),
+        (Tick::from_secs(2.0) + one_frame, "2s 1/24s"),
+        (one_frame, "1/24s"),
+    ] {
+        let json = serde_json::to_string(&Human(tick)).unwrap();
+        assert_eq!(json, format!("{s:?}"));
+        assert_eq!(serde_json::from_str::<Human>(&json).unwrap(), Human(tick));
+    }
+
+    assert_eq!(
+        serde_json::from_str::<Human>(r#""1500ms 1/2s""#).unwrap(),
+        Human(Tick::from_secs(2.0))
+    );
+}
+
+#[test]
+fn test_nanos() {
+    // A third of a second is an integral number of ticks but not of
+    // nanoseconds.
+    let third = Tick::new(TICKS_PER_SECOND / 3);
+    assert_eq!(third.to_nanos(Rounding::Nearest), 333_333_333);
+    assert_eq!(third.to_nanos(Rounding::Ceil), 333_333_334);
+    assert_eq!(
+        Tick::new(-TICKS_PER_SECOND / 3).to_nanos(Rounding::Floor),
+        -333_333_334
+    );
+    assert_eq!(Tick::from_nanos(333_333_333, Rounding::Nearest), Ok(third));
+    assert_eq!(
+        Tick::from_nanos(i128::MAX, Rounding::Nearest),
+        Err(DurationError::Overflow)
+    );
+}
+
+#[test]
+#[cfg(all(feature = "chrono", feature = "time", feature = "jiff"))]
+fn test_duration_interop() {
+    for tick in [
+        Tick::new(0),
+        Tick::new(1),
+        Tick::new(-1),
+        Tick::new(TICKS_PER_SECOND / 3),
+        Tick::from_secs(-12345.5),
+        Tick::new(i64::MAX),
+        Tick::new(i64::MIN),
+    ] {
+        let delta = chrono::TimeDelta::from(tick);
+        assert_eq!(
+            delta.num_nanoseconds().map(i128::from),
+            i64::try_from(tick.to_nanos(Rounding::Nearest))
+                .ok()
+                .map(i128::from)
+        );
+        assert_eq!(Tick::try_from(delta), Ok(tick));
+
+        let duration = time::Duration::from(tick);
+        assert_eq!(
+            duration.whole_nanoseconds(),
+            tick.to_nanos(Rounding::Nearest)
+        );
+        assert_eq!(Tick::try_from(duration), Ok(tick));
+
+        let duration = jiff::SignedDuration::from(tick);
+        assert_eq!(duration.as_nanos(), tick.to_nanos(Rounding::Nearest));
+        assert_eq!(Tick::try_from(duration), Ok(tick));
+    }
+
+    assert_eq!(
+        Tick::try_from(chrono::TimeDelta::MAX),
+        Err(DurationError::Overflow)
+    );
+    assert_eq!(
+        Tick::try_from(time::Duration::MIN),
+        Err(DurationError::Overflow)
+    );
+    assert_eq!(
+        Tick::try_from(jiff::SignedDuration::MAX),
+        Err(DurationError::Overflow)
+    );
+}
@@ -0,0 +1,26 @@
+//! Conversions between [`Tick`] and [`time::Duration`].
+//!
+//! Both directions round to the nearest nanosecond or tick, see
+//! [`Tick::to_nanos()`].
+use crate::{DurationError, NANOS_PER_SECOND, Rounding, Tick};
+use time::Duration;
+
+impl From<Tick> for Duration {
+    /// Every tick is in range of a `Duration`.
+    fn from(tick: Tick) -> Self {
+        let nanos = tick.to_nanos(Rounding::Nearest);
+
+        Duration::new(
+            (nanos / NANOS_PER_SECOND) as _,
+            (nanos % NANOS_PER_SECOND) as _,
+        )
+    }
+}
+
+impl TryFrom<Duration> for Tick {
+    type Error = DurationError;
+
+    fn try_from(duration: Duration) -> Result<Self, Self::Error> {
+        Tick::from_nanos(duration.whole_nanoseconds(), Rounding::Nearest)
+    }
+}
@@ -0,0 +1,263 @@
+//! SMPTE-style timecodes, including drop-frame counting.
+//!
+//! A timecode labels a frame as `HH:MM:SS:FF` at the *nominal* frame rate,
+//! e.g. 30 for 29.97 fps. With drop-frame counting (written `HH:MM:SS;FF`)
+//! frame labels are skipped at the start of every minute except each tenth
+//! so that the labels stay in sync with the wall clock at 1000/1001 rates.
+//!
+//! # Examples
+//!
+//! ```
+//! use frame_tick::{FrameRate, timecode::Timecode};
+//!
+//! let timecode: Timecode = "00:01:00;02".parse().unwrap();
+//! assert!(timecode.drop_frame);
+//! // 00:01:00;00 and 00:01:00;01 do not exist.
+//! assert_eq!(Ok(1800), timecode.to_frame_number(FrameRate::NTSC));
+//! ```
+use crate::{
+    FrameRate, Rounding, TICKS_PER_SECOND, Tick, div_round, frame_start,
+    parse_digits,
+};
+use core::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+/// An error parsing or converting a [`Timecode`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum TimecodeError {
+    /// The string is not of the form `HH:MM:SS:FF` or `HH:MM:SS;FF`.
+    Syntax,
+    /// A field is out of range for the frame rate or the timecode labels a
+    /// frame that is skipped by drop-frame counting.
+    OutOfRange,
+    /// Drop-frame counting was requested at a frame rate that is not a
+    /// multiple of 30000/1001.
+    DropFrameUnsupported,
+    /// The time is before zero.
+    Negative,
+    /// The result does not fit.
+    Overflow,
+}
+
+impl Display for TimecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Syntax => {
+                write!(f, "timecode must be of the form `HH:MM:SS:FF`")
+            }
+            Self::OutOfRange => write!(f, "timecode field out of range"),
+            Self::DropFrameUnsupported => {
+                write!(f, "drop-frame timecode requires a 1000/1001 rate")
+            }
+            Self::Negative => write!(f, "timecode can not be negative"),
+            Self::Overflow => write!(f, "timecode overflows"),
+        }
+    }
+}
+
+impl core::error::Error for TimecodeError {}
+
+/// A timecode label.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Timecode {
+    /// Hours.
+    pub hours: u32,
+    /// Minutes, `0..60`.
+    pub minutes: u8,
+    /// Seconds, `0..60`.
+    pub seconds: u8,
+    /// Frames, `0..nominal_fps`.
+    pub frames: u32,
+    /// Whether this uses drop-frame counting.
+    pub drop_frame: bool,
+}
+
+impl Timecode {
+    /// Create a new timecode.
+    #[inline]
+    pub fn new(
+        hours: u32,
+        minutes: u8,
+        seconds: u8,
+        frames: u32,
+        drop_frame: bool,
+    ) -> Self {
+        Self {
+            hours,
+            minutes,
+            seconds,
+            frames,
+            drop_frame,
+        }
+    }
+
+    /// The label of frame number `frame` at `frame_rate`.
+    pub fn from_frame_number(
+        frame: u64,
+        frame_rate: FrameRate,
+        drop_frame: bool,
+    ) -> Result<Self, TimecodeError> {
+        let nominal = nominal_fps(frame_rate);
+        let mut frame = frame;
+
+        if drop_frame {
+            let dropped = dropped_frames(frame_rate)?;
+            let per_minute = nominal * 60 - dropped;
+            let per_ten_minutes = nominal * 600 - dropped * 9;
+
+            let tens = frame / per_ten_minutes;
+            let rest = frame % per_ten_minutes;
+            frame += dropped * 9 * tens;
+            if rest > dropped {
+                frame += dropped * ((rest - dropped) / per_minute);
+            }
+        }
+
+        let hours = frame / (nominal * 3600);
+        Ok(Self {
+            hours: u32::try_from(hours).map_err(|_| TimecodeError::Overflow)?,
+            minutes: (frame / (nominal * 60) % 60) as _,
+            seconds: (frame / nominal % 60) as _,
+            frames: (frame % nominal) as _,
+            drop_frame,
+        })
+    }
+
+    /// The frame number this labels at `frame_rate`.
+    pub fn to_frame_number(
+        self,
+        frame_rate: FrameRate,
+    ) -> Result<u64, TimecodeError> {
+        let nominal = nominal_fps(frame_rate);
+        if 60 <= self.minutes
+            || 60 <= self.seconds
+            || nominal <= self.frames as u64
+        {
+            return Err(TimecodeError::OutOfRange);
+        }
+
+        let total_minutes = 60 * self.hours as u64 + self.minutes as u64;
+        let frame = (total_minutes * 60 + self.seconds as u64) * nominal
+            + self.frames as u64;
+
+        if self.drop_frame {
+            let dropped = dropped_frames(frame_rate)?;
+            if 0 == self.seconds
+                && (self.frames as u64) < dropped
+                && !self.minutes.is_multiple_of(10)
+            {
+                return Err(TimecodeError::OutOfRange);
+            }
+
+            Ok(frame - dropped * (total_minutes - total_minutes / 10))
+        } else {
+            Ok(frame)
+        }
+    }
+
+    /// The label of the frame containing `tick` at `frame_rate`, with the
+    /// frame found using `rounding`.
+    pub fn from_tick(
+        tick: Tick,
+        frame_rate: FrameRate,
+        drop_frame: bool,
+        rounding: Rounding,
+    ) -> Result<Self, TimecodeError> {
+        let frame = div_round(
+            tick.0 as i128 * frame_rate.num() as i128,
+            TICKS_PER_SECOND as i128 * frame_rate.den() as i128,
+            rounding,
+        );
+        if frame < 0 {
+            return Err(TimecodeError::Negative);
+        }
+
+        Self::from_frame_number(
+            u64::try_from(frame).map_err(|_| TimecodeError::Overflow)?,
+            frame_rate,
+            drop_frame,
+        )
+    }
+
+    /// The first tick of the frame this labels at `frame_rate`.
+    pub fn to_tick(self, frame_rate: FrameRate) -> Result<Tick, TimecodeError> {
+        let frame = self.to_frame_number(frame_rate)?;
+
+        i64::try_from(frame_start(frame as _, frame_rate))
+            .map(Tick)
+            .map_err(|_| TimecodeError::Overflow)
+    }
+}
+
+impl Display for Timecode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:02}:{:02}:{:02}{}{:02}",
+            self.hours,
+            self.minutes,
+            self.seconds,
+            if self.drop_frame { ';' } else { ':' },
+            self.frames
+        )
+    }
+}
+
+impl FromStr for Timecode {
+    type Err = TimecodeError;
+
+    /// Parse `HH:MM:SS:FF`, or `HH:MM:SS;FF` resp. `HH:MM:SS.FF` for
+    /// drop-frame counting.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let split = s.rfind([':', ';', '.']).ok_or(TimecodeError::Syntax)?;
+        let drop_frame = ':' != s.as_bytes()[split] as char;
+
+        let mut fields = s[..split].splitn(3, [':', ';']);
+        let mut field = || {
+            fields
+                .next()
+                .and_then(parse_digits)
+                .ok_or(TimecodeError::Syntax)
+        };
+        let (hours, minutes, seconds) = (field()?, field()?, field()?);
+        let frames =
+            parse_digits(&s[split + 1..]).ok_or(TimecodeError::Syntax)?;
+
+        Ok(Self {
+            hours: u32::try_from(hours).map_err(|_| TimecodeError::Overflow)?,
+            minutes: u8::try_from(minutes)
+                .map_err(|_| TimecodeError::OutOfRange)?,
+            seconds: u8::try_from(seconds)
+                .map_err(|_| TimecodeError::OutOfRange)?,
+            frames: u32::try_from(frames)
+                .map_err(|_| TimecodeError::OutOfRange)?,
+            drop_frame,
+        })
+    }
+}
+
+/// The nominal (integer) frame rate used to count frames in a timecode.
+///
+/// This is the frame rate rounded up, e.g. 30 for 29.97 fps.
+#[inline]
+pub const fn nominal_fps(frame_rate: FrameRate) -> u64 {
+    (frame_rate.num() as u64).div_ceil(frame_rate.den() as u64)
+}
+
+/// Whether `frame_rate` supports drop-frame counting, i.e. whether it is a
+/// multiple of 30000/1001.
+#[inline]
+pub const fn supports_drop_frame(frame_rate: FrameRate) -> bool {
+    1001 == frame_rate.den() && nominal_fps(frame_rate).is_multiple_of(30)
+}
+
+/// Frame labels skipped per minute with drop-frame counting.
+fn dropped_frames(frame_rate: FrameRate) -> Result<u64, TimecodeError> {
+    if supports_drop_frame(frame_rate) {
+        Ok(nominal_fps(frame_rate) / 15)
+    } else {
+        Err(TimecodeError::DropFrameUnsupported)
+    }
+}
@@ -0,0 +1,678 @@
+//! A minimal editorial timeline: tracks of clips, gaps and transitions.
+//!
+//! A [`Track`] is a sequence of [`Item`]s, each starting where the previous
+//! one ends, so the record range of an item follows from the durations
+//! before it. A [`Clip`] plays `duration` ticks of its media, starting at
+//! `source_start`. A [`Transition`] overlaps the cut into the clip that
+//! carries it.
+//!
+//! Edits take record times and item indices and keep the track contiguous:
+//! gaps are inserted or merged as needed. Transitions that no longer fit
+//! into their clip and the item before it are shortened. The source media's
+//! length is not tracked, so slip, roll and slide edits do not check for
+//! handles.
+//!
+//! # Examples
+//!
+//! ```
+//! use frame_tick::{
+//!     FrameRate, Tick,
+//!     timeline::{Clip, Item, Layer, Timeline, Track},
+//! };
+//!
+//! let second = Tick::from_secs_int(1);
+//! let mut track = Track::new();
+//! track.push(Item::Clip(Clip::new("a", Tick::new(0), second * 2)))?;
+//! track.push(Item::Clip(Clip::new("b", second * 10, second * 2)))?;
+//!
+//! // Insert a one second gap after the first second; `b` moves later.
+//! track.insert(second, Item::Gap(second))?;
+//! assert_eq!(second * 5, track.duration());
+//!
+//! // Move the cut into `b` one second later, extending the second half of
+//! // `a`.
+//! track.roll(3, second)?;
+//!
+//! let mut timeline = Timeline::new(FrameRate::FILM);
+//! timeline.push_track(track);
+//!
+//! let Some((0, Layer::Clip(at))) = timeline.visible_at(second * 4) else {
+//!     panic!("expected a clip");
+//! };
+//! assert_eq!("b", at.clip.media);
+//! assert_eq!(second * 11, at.source_time);
+//! assert!(timeline.visible_at(Tick::from_secs(1.5)).is_none());
+//! # Ok::<(), frame_tick::timeline::TimelineError>(())
+//! ```
+use crate::{FrameRate, Tick, frame::frame_start};
+use alloc::vec::Vec;
+use core::{
+    fmt::{self, Display, Formatter},
+    ops::Range,
+};
+
+/// An error editing a [`Track`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum TimelineError {
+    /// The record time is before the start of the track.
+    Negative,
+    /// An item would not have a positive duration.
+    Duration,
+    /// There is no item at this index.
+    Index,
+    /// The item is not a clip.
+    NotAClip,
+    /// The edit needs an item on both sides.
+    NoNeighbor,
+    /// A transition is longer than the items it overlaps.
+    TransitionTooLong,
+}
+
+impl Display for TimelineError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Negative => {
+                write!(f, "record time is before the start of the track")
+            }
+            Self::Duration => write!(f, "item duration must be positive"),
+            Self::Index => write!(f, "no item at this index"),
+            Self::NotAClip => write!(f, "item is not a clip"),
+            Self::NoNeighbor => write!(f, "edit needs an item on both sides"),
+            Self::TransitionTooLong => {
+                write!(f, "transition is longer than the items it overlaps")
+            }
+        }
+    }
+}
+
+impl core::error::Error for TimelineError {}
+
+/// A dissolve or wipe from the previous item into a clip.
+///
+/// It starts `in_offset` before the cut and ends `out_offset` after it.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Transition {
+    /// The part of the transition before the cut.
+    pub in_offset: Tick,
+    /// The part of the transition after the cut.
+    pub out_offset: Tick,
+}
+
+impl Transition {
+    /// The length of the transition.
+    #[inline]
+    pub fn duration(&self) -> Tick {
+        self.in_offset + self.out_offset
+    }
+}
+
+/// A range of some media, placed on a [`Track`].
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct Clip<T> {
+    /// The media, e.g. a path or an id.
+    pub media: T,
+    /// The time in the media the clip starts at.
+    pub source_start: Tick,
+    /// The length of the clip.
+    pub duration: Tick,
+    /// The transition into this clip, if any.
+    pub transition: Option<Transition>,
+}
+
+impl<T> Clip<T> {
+    /// A clip without a transition.
+    #[inline]
+    pub fn new(media: T, source_start: Tick, duration: Tick) -> Self {
+        Self {
+            media,
+            source_start,
+            duration,
+            transition: None,
+        }
+    }
+
+    /// The range of the media this clip plays.
+    #[inline]
+    pub fn source_range(&self) -> Range<Tick> {
+        self.source_start..self.source_start + self.duration
+    }
+}
+
+/// An element of a [`Track`].
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Item<T> {
+    /// A clip.
+    Clip(Clip<T>),
+    /// Nothing, for the given duration.
+    Gap(Tick),
+}
+
+impl<T> Item<T> {
+    /// The length of the item.
+    #[inline]
+    pub fn duration(&self) -> Tick {
+        match self {
+            Self::Clip(clip) => clip.duration,
+            Self::Gap(duration) => *duration,
+        }
+    }
+
+    /// The clip, unless this is a gap.
+    #[inline]
+    pub fn as_clip(&self) -> Option<&Clip<T>> {
+        match self {
+            Self::Clip(clip) => Some(clip),
+            Self::Gap(_) => None,
+        }
+    }
+
+    fn set_duration(&mut self, duration: Tick) {
+        match self {
+            Self::Clip(clip) => clip.duration = duration,
+            Self::Gap(gap) => *gap = duration,
+        }
+    }
+
+    /// Lengthens or shortens the item at its start.
+    fn trim_start(&mut self, delta: Tick) {
+        match self {
+            Self::Clip(clip) => {
+                clip.source_start += delta;
+                clip.duration -= delta;
+            }
+            Self::Gap(gap) => *gap -= delta,
+        }
+    }
+}
+
+impl<T: Clone> Item<T> {
+    /// Splits the item `offset` after its start and returns the second half.
+    fn split_off(&mut self, offset: Tick) -> Self {
+        let mut right = match self {
+            Self::Clip(clip) => Self::Clip(Clip::new(
+                clip.media.clone(),
+                clip.source_start,
+                clip.duration,
+            )),
+            Self::Gap(duration) => Self::Gap(*duration),
+        };
+        right.trim_start(offset);
+        self.set_duration(offset);
+        right
+    }
+}
+
+/// A clip at a record time.
+#[derive(Debug, Eq, PartialEq, Hash)]
+pub struct ClipAt<'a, T> {
+    /// The index of the clip in its track.
+    pub index: usize,
+    /// The clip.
+    pub clip: &'a Clip<T>,
+    /// The time in the clip's media. During a transition this can lie
+    /// outside of the clip's [source range](Clip::source_range).
+    pub source_time: Tick,
+}
+
+impl<T> Clone for ClipAt<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ClipAt<'_, T> {}
+
+/// What a [`Track`] shows at a record time.
+#[derive(Debug, Eq, PartialEq, Hash)]
+pub enum Layer<'a, T> {
+    /// A single clip.
+    Clip(ClipAt<'a, T>),
+    /// A transition between two items. A gap on either side is `None`.
+    Transition {
+        /// The outgoing clip.
+        from: Option<ClipAt<'a, T>>,
+        /// The incoming clip, which carries the transition.
+        to: Option<ClipAt<'a, T>>,
+        /// How far into the transition the record time is.
+        elapsed: Tick,
+        /// The length of the transition.
+        duration: Tick,
+    },
+}
+
+impl<T> Clone for Layer<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Layer<'_, T> {}
+
+/// A sequence of clips and gaps, see the [module documentation](self).
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Track<T> {
+    items: Vec<Item<T>>,
+}
+
+impl<T> Default for Track<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Track<T> {
+    /// An empty track.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { items: Vec::new() }
+    }
+
+    /// The items, in record order.
+    #[inline]
+    pub fn items(&self) -> &[Item<T>] {
+        &self.items
+    }
+
+    /// The total length of the items.
+    #[inline]
+    pub fn duration(&self) -> Tick {
+        self.items.iter().map(Item::duration).sum()
+    }
+
+    /// The record range of the item at `index`.
+    pub fn record_range(&self, index: usize) -> Option<Range<Tick>> {
+        let item = self.items.get(index)?;
+        let start: Tick = self.items[..index].iter().map(Item::duration).sum();
+        Some(start..start + item.duration())
+    }
+
+    /// The index of the item at `tick`, and its record start.
+    pub fn item_at(&self, tick: Tick) -> Option<(usize, Tick)> {
+        if tick < Tick(0) {
+            return None;
+        }
+        let mut start = Tick(0);
+        for (index, item) in self.items.iter().enumerate() {
+            let end = start + item.duration();
+            if tick < end {
+                return Some((index, start));
+            }
+            start = end;
+        }
+        None
+    }
+
+    /// What the track shows at `tick`; `None` for a gap or outside of the
+    /// track.
+    pub fn at(&self, tick: Tick) -> Option<Layer<'_, T>> {
+        let (index, start) = self.item_at(tick)?;
+        let end = start + self.items[index].duration();
+        let clip_at = |index: usize, start: Tick| {
+            self.items[index].as_clip().map(|clip| ClipAt {
+                index,
+                clip,
+                source_time: clip.source_start + (tick - start),
+            })
+        };
+        let transition_into = |to: usize, cut: Tick| {
+            self.items.get(to)?.as_clip()?.transition.and_then(|t| {
+                let from = cut - t.in_offset;
+                (from <= tick && tick < cut + t.out_offset).then_some((from, t))
+            })
+        };
+
+        // The transition into the next item, before its cut.
+        if let Some((from, transition)) = transition_into(index + 1, end) {
+            return Some(Layer::Transition {
+                from: clip_at(index, start),
+                to: clip_at(index + 1, end),
+                elapsed: tick - from,
+                duration: transition.duration(),
+            });
+        }
+        // The transition into this item, after its cut.
+        if 0 < index
+            && let Some((from, transition)) = transition_into(index, start)
+        {
+            let previous = start - self.items[index - 1].duration();
+            return Some(Layer::Transition {
+                from: clip_at(index - 1, previous),
+                to: clip_at(index, start),
+                elapsed: tick - from,
+                duration: transition.duration(),
+            });
+        }
+        clip_at(index, start).map(Layer::Clip)
+    }
+
+    fn item_mut(
+        &mut self,
+        index: usize,
+    ) -> Result<&mut Item<T>, TimelineError> {
+        self.items.get_mut(index).ok_or(TimelineError::Index)
+    }
+
+    /// Merges adjacent gaps and shortens transitions to fit into their
+    /// clip and the item before it.
+    fn fit(&mut self) {
+        self.items
+            .dedup_by(|next, previous| match (previous, next) {
+                (Item::Gap(previous), Item::Gap(next)) => {
+                    *previous += *next;
+                    true
+                }
+                _ => false,
+            });
+
+        let mut previous = Tick(0);
+        for item in &mut self.items {
+            let duration = item.duration();
+            if let Item::Clip(Clip {
+                transition: Some(transition),
+                ..
+            }) = item
+            {
+                transition.in_offset = transition.in_offset.min(previous);
+                transition.out_offset = transition.out_offset.min(duration);
+            }
+            previous = duration;
+        }
+    }
+
+    /// Appends `item` to the end of the track.
+    pub fn push(&mut self, item: Item<T>) -> Result<(), TimelineError> {
+        if item.duration() <= Tick(0) {
+            return Err(TimelineError::Duration);
+        }
+        self.items.push(item);
+        self.fit();
+        Ok(())
+    }
+
+    /// Removes the item at `index`, moving all later items earlier.
+    pub fn remove(&mut self, index: usize) -> Result<Item<T>, TimelineError> {
+        if self.items.len() <= index {
+            return Err(TimelineError::Index);
+        }
+        let item = self.items.remove(index);
+        self.fit();
+        Ok(item)
+    }
+
+    /// Moves the cut before the item at `index` by `delta`, lengthening one
+    /// neighbor and shortening the other. The record range of the track
+    /// does not change.
+    pub fn roll(
+        &mut self,
+        index: usize,
+        delta: Tick,
+    ) -> Result<(), TimelineError> {
+        self.check_trim(index.wrapping_sub(1), delta, index, delta)?;
+        let previous = &mut self.items[index - 1];
+        previous.set_duration(previous.duration() + delta);
+        self.items[index].trim_start(delta);
+        self.fit();
+        Ok(())
+    }
+
+    /// Moves the media of the clip at `index` by `delta`, keeping its record
+    /// range.
+    pub fn slip(
+        &mut self,
+        index: usize,
+        delta: Tick,
+    ) -> Result<(), TimelineError> {
+        match self.item_mut(index)? {
+            Item::Clip(clip) => {
+                clip.source_start += delta;
+                Ok(())
+            }
+            Item::Gap(_) => Err(TimelineError::NotAClip),
+        }
+    }
+
+    /// Moves the item at `index` by `delta`, keeping its media, by
+    /// lengthening one neighbor and shortening the other.
+    pub fn slide(
+        &mut self,
+        index: usize,
+        delta: Tick,
+    ) -> Result<(), TimelineError> {
+        self.item_mut(index)?;
+        self.check_trim(index.wrapping_sub(1), delta, index + 1, delta)?;
+        let previous = &mut self.items[index - 1];
+        previous.set_duration(previous.duration() + delta);
+        self.items[index + 1].trim_start(delta);
+        self.fit();
+        Ok(())
+    }
+
+    /// Checks that lengthening the item at `previous` and trimming the start
+    /// of the item at `next` by `delta` leaves both with a positive duration.
+    fn check_trim(
+        &self,
+        previous: usize,
+        previous_delta: Tick,
+        next: usize,
+        next_delta: Tick,
+    ) -> Result<(), TimelineError> {
+        let (Some(previous), Some(next)) =
+            (self.items.get(previous), self.items.get(next))
+        else {
+            return Err(if self.items.len() <= next.min(previous) {
+                TimelineError::Index
+            } else {
+                TimelineError::NoNeighbor
+            });
+        };
+        if previous.duration() + previous_delta <= Tick(0)
+            || next.duration() - next_delta <= Tick(0)
+        {
+            return Err(TimelineError::Duration);
+        }
+        Ok(())
+    }
+
+    /// Sets or removes the transition into the clip at `index`.
+    ///
+    /// The transition must fit into the clip and the item before it.
+    pub fn set_transition(
+        &mut self,
+        index: usize,
+        transition: Option<Transition>,
+    ) -> Result<(), TimelineError> {
+        let previous = match index {
+            0 => Tick(0),
+            _ => self.items.get(index - 1).map_or(Tick(0), Item::duration),
+        };
+        let Item::Clip(clip) = self.item_mut(index)? else {
+            return Err(TimelineError::NotAClip);
+        };
+        if let Some(Transition {
+            in_offset,
+            out_offset,
+        }) = transition
+        {
+            if in_offset < Tick(0) || out_offset < Tick(0) {
+                return Err(TimelineError::Duration);
+            }
+            if previous < in_offset || clip.duration < out_offset {
+                return Err(TimelineError::TransitionTooLong);
+            }
+        }
+        clip.transition = transition;
+        Ok(())
+    }
+}
+
+impl<T: Clone> Track<T> {
+    /// Splits the item at `at` and returns the index of the item starting
+    /// there. Pads the track with a gap if it ends before `at`.
+    fn split(&mut self, at: Tick) -> usize {
+        let mut start = Tick(0);
+        for index in 0..self.items.len() {
+            if at == start {
+                return index;
+            }
+            let end = start + self.items[index].duration();
+            if at < end {
+                let right = self.items[index].split_off(at - start);
+                self.items.insert(index + 1, right);
+                return index + 1;
+            }
+            start = end;
+        }
+        if start < at {
+            self.items.push(Item::Gap(at - start));
+        }
+        self.items.len()
+    }
+
+    fn check_edit(at: Tick, item: &Item<T>) -> Result<(), TimelineError> {
+        if at < Tick(0) {
+            Err(TimelineError::Negative)
+        } else if item.duration() <= Tick(0) {
+            Err(TimelineError::Duration)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Inserts `item` at `at`, splitting the item there and moving all later
+    /// items by the item's duration.
+    pub fn insert(
+        &mut self,
+        at: Tick,
+        item: Item<T>,
+    ) -> Result<(), TimelineError> {
+        Self::check_edit(at, &item)?;
+        let index = self.split(at);
+        self.items.insert(index, item);
+        self.fit();
+        Ok(())
+    }
+
+    /// Places `item` at `at`, replacing whatever was there before.
+    pub fn overwrite(
+        &mut self,
+        at: Tick,
+        item: Item<T>,
+    ) -> Result<(), TimelineError> {
+        Self::check_edit(at, &item)?;
+        let start = self.split(at);
+        let end = self.split(at + item.duration());
+        self.items.splice(start..end, [item]);
+        self.fit();
+        Ok(())
+    }
+
+    /// Replaces `range` with a gap.
+    pub fn lift(&mut self, range: Range<Tick>) -> Result<(), TimelineError> {
+        if range.is_empty() {
+            return Ok(());
+        }
+        let duration = range.end - range.start;
+        self.overwrite(range.start, Item::Gap(duration))
+    }
+
+    /// Removes `range`, moving all later items earlier.
+    pub fn ripple_delete(
+        &mut self,
+        range: Range<Tick>,
+    ) -> Result<(), TimelineError> {
+        if range.start < Tick(0) {
+            return Err(TimelineError::Negative);
+        }
+        let end = range.end.min(self.duration());
+        if end <= range.start {
+            return Ok(());
+        }
+        let start = self.split(range.start);
+        let end = self.split(end);
+        self.items.drain(start..end);
+        self.fit();
+        Ok(())
+    }
+}
+
+/// Tracks stacked on top of each other, see the
+/// [module documentation](self).
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct Timeline<T> {
+    frame_rate: FrameRate,
+    tracks: Vec<Track<T>>,
+}
+
+impl<T> Timeline<T> {
+    /// An empty timeline, edited at `frame_rate`.
+    #[inline]
+    pub const fn new(frame_rate: FrameRate) -> Self {
+        Self {
+            frame_rate,
+            tracks: Vec::new(),
+        }
+    }
+
+    /// The frame rate.
+    #[inline]
+    pub const fn frame_rate(&self) -> FrameRate {
+        self.frame_rate
+    }
+
+    /// The tracks, from bottom to top.
+    #[inline]
+    pub fn tracks(&self) -> &[Track<T>] {
+        &self.tracks
+    }
+
+    /// The track at `index`, for editing.
+    #[inline]
+    pub fn track_mut(&mut self, index: usize) -> Option<&mut Track<T>> {
+        self.tracks.get_mut(index)
+    }
+
+    /// Adds `track` on top and returns its index.
+    pub fn push_track(&mut self, track: Track<T>) -> usize {
+        self.tracks.push(track);
+        self.tracks.len() - 1
+    }
+
+    /// The length of the longest track.
+    pub fn duration(&self) -> Tick {
+        self.tracks
+            .iter()
+            .map(Track::duration)
+            .max()
+            .unwrap_or(Tick(0))
+    }
+
+    /// What each track shows at `tick`, with the track index, from bottom
+    /// to top. Tracks with a gap at `tick` are skipped.
+    pub fn layers_at(
+        &self,
+        tick: Tick,
+    ) -> impl DoubleEndedIterator<Item = (usize, Layer<'_, T>)> {
+        self.tracks
+            .iter()
+            .enumerate()
+            .filter_map(move |(index, track)| Some((index, track.at(tick)?)))
+    }
+
+    /// The topmost layer at `tick`, with its track index.
+    #[inline]
+    pub fn visible_at(&self, tick: Tick) -> Option<(usize, Layer<'_, T>)> {
+        self.layers_at(tick).next_back()
+    }
+
+    /// The topmost layer at the start of `frame`.
+    #[inline]
+    pub fn visible_at_frame(
+        &self,
+        frame: i64,
+    ) -> Option<(usize, Layer<'_, T>)> {
+        self.visible_at(frame_start(frame, self.frame_rate))
+    }
+}
@@ -0,0 +1,556 @@
+//! Variable frame rate timestamp tables.
+//!
+//! Screen recordings and phone footage often have no constant frame rate.
+//! A [`VfrTimeline`] instead maps each frame number to the tick it is
+//! presented at via a table of per-frame timestamps.
+//!
+//! Tables can be read from and written to the Matroska timestamp text
+//! formats, as used by `mkvmerge --timestamps` and `mkvextract`:
+//!
+//! - v1 lists ranges of frames with a constant rate, plus a default rate for
+//!   all other frames.
+//!
+//! - v2 lists the timestamp of each frame in milliseconds.
+//!
+//! # Examples
+//!
+//! ```
+//! use frame_tick::{FrameRate, Tick, frame::frame_start, vfr::VfrTimeline};
+//!
+//! let timeline = VfrTimeline::from_v2(
+//!     "# timecode format v2\n0\n41.708\n83.417\n125.125\n",
+//! )?;
+//! assert_eq!(4, timeline.len());
+//! assert_eq!(Some(2), timeline.frame_at(Tick::from_secs(0.1)));
+//! assert_eq!(Some(FrameRate::NTSC_FILM), timeline.nearest_standard_rate());
+//!
+//! // 100 frames at 23.976, then 100 at 59.94.
+//! let timeline = VfrTimeline::from_v1(
+//!     "# timecode format v1\nAssume 23.976\n100,199,59.94\n",
+//!     300,
+//! )?;
+//! assert_eq!(
+//!     Some(
+//!         frame_start(100, FrameRate::NTSC_FILM)
+//!             + frame_start(100, FrameRate::NTSC_HIGH)
+//!     ),
+//!     timeline.frame_start(200)
+//! );
+//! # Ok::<(), frame_tick::vfr::VfrError>(())
+//! ```
+use crate::{
+    FrameRate, Rounding, TICKS_PER_SECOND, Tick, best_rational, catalog,
+    div_round, frame::frame_start, parse_digits,
+};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{self, Display, Formatter, Write};
+
+/// An error reading, writing or building a [`VfrTimeline`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum VfrError {
+    /// The first line is not a `# timecode format` header of the expected
+    /// version.
+    Header,
+    /// The line, counted from one, is malformed.
+    Syntax {
+        /// The line number.
+        line: usize,
+    },
+    /// The timestamp of the frame is not after the one of the frame before.
+    NotIncreasing {
+        /// The frame number.
+        frame: usize,
+    },
+    /// A timestamp does not fit into a [`Tick`].
+    Overflow,
+    /// The v1 format can only express tables starting at zero.
+    Offset,
+    /// A frame duration can not be written as a decimal frame rate.
+    Inexact {
+        /// The frame number.
+        frame: usize,
+    },
+    /// The table does not fit into memory.
+    Capacity,
+}
+
+impl Display for VfrError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Header => {
+                write!(f, "missing or unsupported timestamp header")
+            }
+            Self::Syntax { line } => {
+                write!(f, "malformed timestamp file at line {line}")
+            }
+            Self::NotIncreasing { frame } => {
+                write!(f, "timestamp of frame {frame} is not increasing")
+            }
+            Self::Overflow => write!(f, "timestamp overflows a tick"),
+            Self::Offset => write!(f, "v1 timestamps must start at zero"),
+            Self::Inexact { frame } => write!(
+                f,
+                "duration of frame {frame} is not a decimal frame rate"
+            ),
+            Self::Capacity => write!(f, "timestamp table too large"),
+        }
+    }
+}
+
+impl core::error::Error for VfrError {}
+
+/// A table of per-frame presentation timestamps, see the
+/// [module documentation](self).
+///
+/// Timestamps strictly increase. The last frame lasts indefinitely.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct VfrTimeline {
+    timestamps: Vec<Tick>,
+}
+
+impl TryFrom<Vec<Tick>> for VfrTimeline {
+    type Error = VfrError;
+
+    #[inline]
+    fn try_from(timestamps: Vec<Tick>) -> Result<Self, Self::Error> {
+        Self::new(timestamps)
+    }
+}
+
+impl VfrTimeline {
+    /// A table of the start of each frame.
+    pub fn new(timestamps: Vec<Tick>) -> Result<Self, VfrError> {
+        match timestamps.windows(2).position(|pair| pair[1] <= pair[0]) {
+            Some(frame) => Err(VfrError::NotIncreasing { frame: frame + 1 }),
+            None => Ok(Self { timestamps }),
+        }
+    }
+
+    /// `frames` frames at the constant rate `frame_rate`.
+    pub fn from_frame_rate(frames: usize, frame_rate: FrameRate) -> Self {
+        Self {
+            timestamps: (0..frames as i64)
+                .map(|frame| frame_start(frame, frame_rate))
+                .collect(),
+        }
+    }
+
+    /// The timestamps, one per frame.
+    #[inline]
+    pub fn timestamps(&self) -> &[Tick] {
+        &self.timestamps
+    }
+
+    /// The number of frames.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.timestamps.len()
+    }
+
+    /// Returns `true` if there are no frames.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.timestamps.is_empty()
+    }
+
+    /// The first tick of `frame`.
+    #[inline]
+    pub fn frame_start(&self, frame: usize) -> Option<Tick> {
+        self.timestamps.get(frame).copied()
+    }
+
+    /// The first tick after `frame`. `None` for the last frame.
+    #[inline]
+    pub fn frame_end(&self, frame: usize) -> Option<Tick> {
+        self.frame_start(frame.checked_add(1)?)
+    }
+
+    /// The frame shown at `tick`. `None` before the first frame.
+    #[inline]
+    pub fn frame_at(&self, tick: Tick) -> Option<usize> {
+        self.timestamps
+            .partition_point(|&start| start <= tick)
+            .checked_sub(1)
+    }
+
+    /// The frame whose start is nearest to `tick`, the later one on a tie.
+    pub fn nearest_frame(&self, tick: Tick) -> Option<usize> {
+        let after = self.timestamps.partition_point(|&start| start < tick);
+        match (after.checked_sub(1), self.timestamps.get(after)) {
+            (Some(before), Some(&start))
+                if tick - self.timestamps[before] < start - tick =>
+            {
+                Some(before)
+            }
+            (before, None) => before,
+            (_, Some(_)) => Some(after),
+        }
+    }
+
+    /// The time from the first to the last frame's start.
+    #[inline]
+    pub fn duration(&self) -> Tick {
+        match (self.timestamps.first(), self.timestamps.last()) {
+            (Some(&first), Some(&last)) => last - first,
+            _ => Tick(0),
+        }
+    }
+
+    /// The average frame rate, i.e. the frames between the first and the
+    /// last frame's start per second.
+    ///
+    /// Timestamps are rounded to ticks, so this is the rate with a
+    /// denominator of at most 1001 that spans the same time to within a
+    /// tick, if any, and otherwise the exact rate if it fits a
+    /// [`FrameRate`]. `None` for fewer than two frames.
+    pub fn average_frame_rate(&self) -> Option<FrameRate> {
+        let frames = self.len().checked_sub(1)?;
+        let ticks = self.duration().0;
+        let numer = (frames as u64).checked_mul(TICKS_PER_SECOND as _)?;
+        let simple = best_rational(numer as f64 / ticks as f64, 1001)
+            .and_then(|(num, den)| FrameRate::from_u64(num, den));
+        let spans = |rate: FrameRate| {
+            (frame_start(frames as _, rate).0 - ticks).abs() <= 1
+        };
+        simple
+            .filter(|&rate| spans(rate))
+            .or_else(|| FrameRate::from_u64(numer, ticks as _))
+            .or(simple)
+    }
+
+    /// The rate from the [`catalog`] closest to the
+    /// [average](Self::average_frame_rate).
+    pub fn nearest_standard_rate(&self) -> Option<FrameRate> {
+        let average = self.average_frame_rate()?.to_f64();
+        // The ratio of the larger to the smaller rate.
+        let distance = |rate: FrameRate| {
+            let ratio = rate.to_f64() / average;
+            ratio.max(1.0 / ratio)
+        };
+        catalog::iter()
+            .map(|entry| entry.frame_rate())
+            .min_by(|&a, &b| distance(a).total_cmp(&distance(b)))
+    }
+
+    /// Reads the Matroska timestamp format v2: a header line followed by
+    /// the timestamp of each frame in milliseconds.
+    ///
+    /// Empty lines and `#` comments are skipped.
+    pub fn from_v2(text: &str) -> Result<Self, VfrError> {
+        let mut timestamps = Vec::new();
+        for (line, text) in header(text, "v2")? {
+            let ticks = parse_millis(text).ok_or(VfrError::Syntax { line })?;
+            timestamps.push(Tick(
+                i64::try_from(ticks).map_err(|_| VfrError::Overflow)?,
+            ));
+        }
+        Self::new(timestamps)
+    }
+
+    /// Writes the Matroska timestamp format v2.
+    ///
+    /// Milliseconds are written to up to six places, i.e. to the
+    /// nanosecond, which reads back as the same ticks.
+    pub fn to_v2(&self) -> String {
+        let mut text = String::from("# timecode format v2\n");
+        for tick in &self.timestamps {
+            let nanos = tick.to_nanos(Rounding::Nearest);
+            if nanos < 0 {
+                text.push('-');
+            }
+            let (millis, mut fraction) =
+                (nanos.abs() / 1_000_000, nanos.abs() % 1_000_000);
+            // Writing to a `String` can not fail.
+            let _ = write!(text, "{millis}");
+            if 0 != fraction {
+                let mut digits = 6;
+                while 0 == fraction % 10 {
+                    fraction /= 10;
+                    digits -= 1;
+                }
+                let _ = write!(text, ".{fraction:0digits$}");
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Reads the Matroska timestamp format v1 for `frames` frames.
+    ///
+    /// After the header, an `Assume <rate>` line gives the default rate.
+    /// Lines of the form `<first>,<last>,<rate>` give the rate of an
+    /// inclusive range of frames. The timestamps of each range, and of each
+    /// run of frames between ranges, are counted exactly from its start.
+    ///
+    /// Rates are read with [`FrameRate::from_str()`](core::str::FromStr), so
+    /// `23.976` is snapped to `24000/1001`. `mkvmerge` takes such rates
+    /// literally, which drifts by a few milliseconds per hour.
+    ///
+    /// Fails with [`VfrError::Overflow`] if the timestamps do not fit into a
+    /// [`Tick`] and with [`VfrError::Capacity`] if the table can not be
+    /// allocated.
+    pub fn from_v1(text: &str, frames: usize) -> Result<Self, VfrError> {
+        let mut lines = header(text, "v1")?;
+        let (line, assume) =
+            lines.next().ok_or(VfrError::Syntax { line: 2 })?;
+        let assume = assume
+            .strip_prefix("Assume ")
+            .or_else(|| assume.strip_prefix("assume "))
+            .and_then(|rate| rate.trim().parse::<FrameRate>().ok())
+            .ok_or(VfrError::Syntax { line })?;
+
+        let mut ranges = Vec::new();
+        for (line, text) in lines {
+            let (first, end, rate) =
+                parse_range(text).ok_or(VfrError::Syntax { line })?;
+            ranges.push((first, end, rate, line));
+        }
+        ranges.sort_by_key(|&(first, ..)| first);
+        if let Some(pair) = ranges.windows(2).find(|pair| pair[1].0 < pair[0].1)
+        {
+            return Err(VfrError::Syntax { line: pair[1].3 });
+        }
+
+        // Runs of frames at one rate, including those between ranges, as
+        // their end and rate.
+        let mut runs = Vec::new();
+        let mut end = 0;
+        for &(first, range_end, rate, _) in &ranges {
+            if end < first {
+                runs.push((first, assume));
+            }
+            runs.push((range_end, rate));
+            end = range_end;
+        }
+        runs.push((usize::MAX, assume));
+
+        // Check that the table ends within the range of a tick before
+        // allocating it.
+        let mut first = 0;
+        let mut last = 0i128;
+        for (end, rate) in &mut runs {
+            *end = frames.min(*end);
+            last += crate::frame_start(end.saturating_sub(first) as _, *rate);
+            first = first.max(*end);
+        }
+        if last > i64::MAX as i128 {
+            return Err(VfrError::Overflow);
+        }
+
+        let mut timestamps = Vec::new();
+        timestamps
+            .try_reserve_exact(frames)
+            .map_err(|_| VfrError::Capacity)?;
+        let mut start = 0i128;
+        let mut first = 0;
+        for (end, rate) in runs {
+            if end <= first {
+                continue;
+            }
+            for frame in 0..(end - first) as i128 {
+                timestamps
+                    .push(Tick((start + crate::frame_start(frame, rate)) as _));
+            }
+            start += crate::frame_start((end - first) as _, rate);
+            first = end;
+        }
+        Self::new(timestamps)
+    }
+
+    /// Writes the Matroska timestamp format v1.
+    ///
+    /// Each run of frames on the frame grid of one rate becomes a range.
+    /// The rate of the last run is assumed for any frames after the table.
+    /// Reading the result for [`len()`](Self::len) frames gives the same
+    /// timestamps.
+    ///
+    /// Fails if the first timestamp is not zero or a frame duration can not
+    /// be written as a decimal rate of at most nine places.
+    pub fn to_v1(&self) -> Result<String, VfrError> {
+        if self
+            .timestamps
+            .first()
+            .is_some_and(|&first| first != Tick(0))
+        {
+            return Err(VfrError::Offset);
+        }
+
+        let mut ranges = String::new();
+        let mut assume = None;
+        let mut first = 0;
+        // A single frame left over is covered by the `Assume` line.
+        while first + 1 < self.len() {
+            let (rate, decimal) = self.run_rate(first)?;
+            let start = self.timestamps[first];
+            let on_grid = |frame: usize| {
+                self.timestamps[frame] - start
+                    == frame_start((frame - first) as _, rate)
+            };
+
+            // The start of the next run must be on this run's grid too.
+            let mut next = first + 1;
+            while next + 1 < self.len() && on_grid(next + 1) {
+                next += 1;
+            }
+            if next + 1 == self.len() {
+                next = self.len();
+            }
+            let _ = writeln!(ranges, "{first},{},{decimal}", next - 1);
+            assume = Some(decimal);
+            first = next;
+        }
+
+        let assume = assume
+            .or_else(|| DecimalRate::shortest(FrameRate::FILM))
+            .ok_or(VfrError::Inexact { frame: 0 })?;
+        Ok(format!("# timecode format v1\nAssume {assume}\n{ranges}"))
+    }
+
+    /// The rate of the run starting at `first`, which has at least two
+    /// frames, as read back from its decimal.
+    fn run_rate(
+        &self,
+        first: usize,
+    ) -> Result<(FrameRate, DecimalRate), VfrError> {
+        let duration = (self.timestamps[first + 1] - self.timestamps[first]).0;
+        let exact = |rate: FrameRate| frame_start(1, rate).0 == duration;
+        let rate = TICKS_PER_SECOND as f64 / duration as f64;
+
+        // Prefer standard rates within a tick per frame, then the rate
+        // rounded up to ever more places.
+        FrameRate::from_f64_snapped(rate, rate / duration as f64)
+            .and_then(DecimalRate::shortest)
+            .into_iter()
+            .chain((0..=9).filter_map(|places| {
+                DecimalRate::ceil(TICKS_PER_SECOND as _, duration as _, places)
+            }))
+            .find_map(|decimal| {
+                decimal
+                    .to_string()
+                    .parse::<FrameRate>()
+                    .ok()
+                    .filter(|&rate| exact(rate))
+                    .map(|rate| (rate, decimal))
+            })
+            .ok_or(VfrError::Inexact { frame: first })
+    }
+}
+
+/// A frame rate written as a decimal.
+#[derive(Copy, Clone, Debug)]
+struct DecimalRate {
+    value: u64,
+    places: u32,
+}
+
+impl DecimalRate {
+    /// `num / den` rounded up to `places` places.
+    fn ceil(num: u64, den: u64, places: u32) -> Option<Self> {
+        let scale = 10u64.pow(places);
+        let value =
+            div_round(num as i128 * scale as i128, den as _, Rounding::Ceil);
+        Some(Self {
+            value: u64::try_from(value).ok()?,
+            places,
+        })
+    }
+
+    /// The shortest decimal that reads back as `rate`.
+    fn shortest(rate: FrameRate) -> Option<Self> {
+        (0..=9).find_map(|places| {
+            let scale = 10u64.pow(places);
+            let value = div_round(
+                rate.num() as i128 * scale as i128,
+                rate.den() as _,
+                Rounding::Nearest,
+            );
+            let decimal = Self {
+                value: u64::try_from(value).ok()?,
+                places,
+            };
+            (Ok(rate) == decimal.to_string().parse()).then_some(decimal)
+        })
+    }
+}
+
+impl Display for DecimalRate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let scale = 10u64.pow(self.places);
+        match self.places {
+            0 => write!(f, "{}", self.value),
+            places => write!(
+                f,
+                "{}.{:0places$}",
+                self.value / scale,
+                self.value % scale,
+                places = places as usize
+            ),
+        }
+    }
+}
+
+/// Checks the header of `text` and returns the numbered lines after it,
+/// without comments and empty lines.
+fn header<'a>(
+    text: &'a str,
+    version: &str,
+) -> Result<impl Iterator<Item = (usize, &'a str)>, VfrError> {
+    let mut lines = text
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()));
+    let (_, first) = lines.next().ok_or(VfrError::Header)?;
+    let format = first
+        .strip_prefix('#')
+        .map(str::trim)
+        .and_then(|first| {
+            first
+                .strip_prefix("timecode format ")
+                .or_else(|| first.strip_prefix("timestamp format "))
+        })
+        .ok_or(VfrError::Header)?;
+    if format.trim() != version {
+        return Err(VfrError::Header);
+    }
+    Ok(lines.filter(|(_, line)| !line.is_empty() && !line.starts_with('#')))
+}
+
+/// Parses `<first>,<last>,<rate>` into the range of frames and its rate.
+fn parse_range(text: &str) -> Option<(usize, usize, FrameRate)> {
+    let mut fields = text.split(',').map(str::trim);
+    let (Some(first), Some(last), Some(rate), None) =
+        (fields.next(), fields.next(), fields.next(), fields.next())
+    else {
+        return None;
+    };
+    let first = usize::try_from(parse_digits(first)?).ok()?;
+    let last = usize::try_from(parse_digits(last)?).ok()?;
+    (first <= last).then_some((first, last.checked_add(1)?, rate.parse().ok()?))
+}
+
+/// Parses milliseconds with up to nine decimal places into the nearest
+/// number of ticks.
+fn parse_millis(text: &str) -> Option<i128> {
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(text) => (true, text),
+        None => (false, text),
+    };
+    let (integer, fraction) = text.split_once('.').unwrap_or((text, "0"));
+    if 9 < fraction.len() {
+        return None;
+    }
+    let scale = 10i128.pow(fraction.len() as _);
+    let value = parse_digits(integer)? as i128 * scale
+        + parse_digits(fraction)? as i128;
+    let value = if negative { -value } else { value };
+
+    Some(div_round(
+        value * TICKS_PER_SECOND as i128,
+        1000 * scale,
+        Rounding::Nearest,
+    ))
+}
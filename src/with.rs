@@ -0,0 +1,517 @@
+//! Alternative [`serde`](https://docs.rs/serde/) representations for use with
+//! `#[serde(with = "...")]`.
+//!
+//! The `serde` derive on [`Tick`] writes the raw number of
+//! ticks and the one on [`FrameRate`] a `{num, den}` map.
+//! The modules in here write forms that are easier to read and edit by hand
+//! instead.
+//!
+//! Deserializing is strict: values that can not be converted exactly, like
+//! a timecode at a frame rate it was not written for, are rejected.
+//!
+//! # Examples
+//!
+//! ```
+//! # use serde::{Deserialize, Serialize};
+//! use frame_tick::{FrameRate, Tick};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Shot {
+//!     #[serde(with = "frame_tick::with::frame_rate")]
+//!     frame_rate: FrameRate,
+//!     #[serde(with = "frame_tick::with::timecode::film")]
+//!     start: Tick,
+//!     #[serde(with = "frame_tick::with::human")]
+//!     handles: Tick,
+//!     #[serde(with = "frame_tick::with::iso8601")]
+//!     timeout: Tick,
+//! }
+//! ```
+use crate::{
+    FrameRate, FrameRateError, Rounding, TICKS_PER_SECOND, Tick, div_round,
+    frame_start, gcd, parse_digits,
+};
+use core::fmt::{self, Display, Formatter};
+use serde::{
+    Deserialize, Deserializer, Serializer,
+    de::{self, Visitor},
+    ser,
+};
+
+/// Generates one module per [`FrameRate`] constant, each forwarding to
+/// `super::serialize()` and `super::deserialize()` with that rate and, if
+/// given, the drop-frame flag.
+macro_rules! frame_rate_modules {
+    (@doc $frame_rate:ident $(, false)?) => {
+        concat!("At [`FrameRate::", stringify!($frame_rate), "`].")
+    };
+    (@doc $frame_rate:ident, true) => {
+        concat!(
+            "At [`FrameRate::",
+            stringify!($frame_rate),
+            "`] with drop-frame counting."
+        )
+    };
+    ($($name:ident => $frame_rate:ident $(, $drop_frame:tt)?;)*) => {
+        $(
+            #[doc = frame_rate_modules!(@doc $frame_rate $(, $drop_frame)?)]
+            pub mod $name {
+                use crate::{FrameRate, Tick};
+                use serde::{Deserializer, Serializer};
+
+                pub fn serialize<S: Serializer>(
+                    tick: &Tick,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error> {
+                    super::serialize(
+                        tick,
+                        FrameRate::$frame_rate,
+                        $($drop_frame,)?
+                        serializer,
+                    )
+                }
+
+                pub fn deserialize<'de, D: Deserializer<'de>>(
+                    deserializer: D,
+                ) -> Result<Tick, D::Error> {
+                    super::deserialize(
+                        deserializer,
+                        FrameRate::$frame_rate,
+                        $($drop_frame,)?
+                    )
+                }
+            }
+        )*
+    };
+}
+
+/// Write a [`Tick`] as an ISO 8601 duration string, e.g.
+/// `"PT1M30S"`.
+///
+/// Serializing fails for ticks that have no terminating decimal
+/// representation in seconds. Deserializing fails for durations that are not
+/// an integral number of ticks.
+pub mod iso8601 {
+    use crate::{Tick, iso8601::Iso8601Duration};
+    use core::fmt::{self, Formatter};
+    use serde::{
+        Deserializer, Serializer,
+        de::{self, Visitor},
+        ser,
+    };
+
+    pub fn serialize<S: Serializer>(
+        tick: &Tick,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(
+            &Iso8601Duration::new(*tick).map_err(ser::Error::custom)?,
+        )
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Tick, D::Error> {
+        struct Iso8601Visitor;
+
+        impl Visitor<'_> for Iso8601Visitor {
+            type Value = Tick;
+
+            fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                write!(f, "an ISO 8601 duration")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Tick, E> {
+                Tick::from_iso8601(value).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(Iso8601Visitor)
+    }
+}
+
+/// Write a [`Tick`] as a number of seconds, e.g. `1.5`.
+///
+/// This is lossy for ticks that are not a binary fraction of a second.
+/// Deserializing rounds to the nearest tick and fails for values that are
+/// not finite or out of range.
+pub mod secs {
+    use crate::{TICKS_PER_SECOND, Tick};
+    use serde::{Deserialize, Deserializer, Serializer, de};
+
+    pub fn serialize<S: Serializer>(
+        tick: &Tick,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(tick.to_secs())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Tick, D::Error> {
+        let secs = f64::deserialize(deserializer)?;
+        let ticks = secs * TICKS_PER_SECOND as f64;
+
+        if !ticks.is_finite() || (i64::MAX as f64) <= ticks.abs() {
+            return Err(de::Error::invalid_value(
+                de::Unexpected::Float(secs),
+                &"a finite number of seconds in range of a tick",
+            ));
+        }
+
+        Ok(Tick::from(ticks))
+    }
+}
+
+/// Write a [`Tick`] as a human-readable duration, e.g.
+/// `"1h 2m 3.5s"`.
+///
+/// Sub-second parts that have no terminating decimal representation are
+/// written as a fraction, e.g. `"2s 1/24s"`, which makes this lossless.
+///
+/// Deserializing accepts any sequence of whitespace separated components
+/// with the units `h`, `m`, `s` and `ms`, each an integer, a decimal or a
+/// fraction, optionally preceded by a `-` for the whole duration.
+pub mod human {
+    use super::*;
+    use crate::iso8601::{is_decimal, write_fraction};
+
+    pub fn serialize<S: Serializer>(
+        tick: &Tick,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&Human(*tick))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Tick, D::Error> {
+        deserializer.deserialize_str(StrVisitor(
+            "a duration like `1h 2m 3.5s`",
+            parse_human,
+        ))
+    }
+
+    struct Human(Tick);
+
+    impl Display for Human {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            let ticks = self.0.0.unsigned_abs();
+            let seconds = ticks / TICKS_PER_SECOND as u64;
+            let fraction = ticks % TICKS_PER_SECOND as u64;
+
+            if 0 == ticks {
+                return f.write_str("0s");
+            }
+            if self.0.0 < 0 {
+                f.write_str("-")?;
+            }
+
+            let mut separator = "";
+            for (value, unit) in
+                [(seconds / 3600, "h"), (seconds / 60 % 60, "m")]
+            {
+                if 0 != value {
+                    write!(f, "{separator}{value}{unit}")?;
+                    separator = " ";
+                }
+            }
+
+            if is_decimal(Tick(fraction as _)) {
+                if !seconds.is_multiple_of(60) || 0 != fraction {
+                    write!(f, "{separator}{}", seconds % 60)?;
+                    write_fraction(f, fraction)?;
+                    f.write_str("s")?;
+                }
+            } else {
+                if !seconds.is_multiple_of(60) {
+                    write!(f, "{separator}{}s", seconds % 60)?;
+                    separator = " ";
+                }
+                let divisor = gcd(fraction, TICKS_PER_SECOND as _);
+                write!(
+                    f,
+                    "{separator}{}/{}s",
+                    fraction / divisor,
+                    TICKS_PER_SECOND as u64 / divisor
+                )?;
+            }
+
+            Ok(())
+        }
+    }
+
+    fn parse_human(s: &str) -> Result<Tick, &'static str> {
+        let (negative, s) = match s.trim_start().strip_prefix('-') {
+            Some(s) => (true, s),
+            None => (false, s),
+        };
+
+        let mut ticks = 0i128;
+        let mut components = 0;
+        for component in s.split_whitespace() {
+            let (number, unit) =
+                if let Some(number) = component.strip_suffix("ms") {
+                    (number, 1000)
+                } else if let Some(number) = component.strip_suffix('s') {
+                    (number, 1)
+                } else if let Some(number) = component.strip_suffix('m') {
+                    (number, -60)
+                } else if let Some(number) = component.strip_suffix('h') {
+                    (number, -3600)
+                } else {
+                    return Err("a duration component without a unit");
+                };
+
+            // value = numer / denom, in units.
+            let (numer, denom) = match number.split_once(['/', '.']) {
+                Some((numer, denom)) if number.contains('/') => {
+                    (parse_digits(numer), parse_digits(denom))
+                }
+                Some((integer, fraction)) => {
+                    let scale = (fraction.len() <= 18)
+                        .then(|| 10u64.pow(fraction.len() as _));
+                    (
+                        parse_digits(integer)
+                            .zip(parse_digits(fraction))
+                            .zip(scale)
+                            .and_then(|((integer, fraction), scale)| {
+                                integer
+                                    .checked_mul(scale)?
+                                    .checked_add(fraction)
+                            }),
+                        scale,
+                    )
+                }
+                None => (parse_digits(number), Some(1)),
+            };
+            let (Some(numer), Some(denom @ 1..)) = (numer, denom) else {
+                return Err("a malformed duration component");
+            };
+
+            // Negative units are multiples of a second, positive ones
+            // fractions.
+            let (numer, denom) = if unit < 0 {
+                (numer as i128 * -unit as i128, denom as i128)
+            } else {
+                (numer as i128, denom as i128 * unit as i128)
+            };
+            let numer = numer
+                .checked_mul(TICKS_PER_SECOND as _)
+                .ok_or("a duration that overflows a tick")?;
+            if 0 != numer % denom {
+                return Err(
+                    "a duration that is not an integral number of ticks",
+                );
+            }
+
+            ticks = ticks
+                .checked_add(numer / denom)
+                .ok_or("a duration that overflows a tick")?;
+            components += 1;
+        }
+
+        if 0 == components {
+            return Err("an empty duration");
+        }
+
+        i64::try_from(if negative { -ticks } else { ticks })
+            .map(Tick)
+            .map_err(|_| "a duration that overflows a tick")
+    }
+}
+
+/// Write a [`Tick`] as a timecode string, e.g. `"01:00:00:00"`.
+///
+/// Pick the submodule for the frame rate, e.g.
+/// `#[serde(with = "frame_tick::with::timecode::pal")]`. Serializing fails for
+/// ticks that are not the first tick of a frame or before zero.
+/// Deserializing fails for timecodes that are invalid at the frame rate or
+/// use a different drop-frame counting.
+pub mod timecode {
+    use super::*;
+    use crate::timecode::Timecode;
+
+    fn serialize<S: Serializer>(
+        tick: &Tick,
+        frame_rate: FrameRate,
+        drop_frame: bool,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let timecode =
+            Timecode::from_tick(*tick, frame_rate, drop_frame, Rounding::Floor)
+                .map_err(ser::Error::custom)?;
+        if Ok(*tick) != timecode.to_tick(frame_rate) {
+            return Err(ser::Error::custom(
+                "tick is not the first tick of a frame",
+            ));
+        }
+
+        serializer.collect_str(&timecode)
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+        frame_rate: FrameRate,
+        drop_frame: bool,
+    ) -> Result<Tick, D::Error> {
+        struct TimecodeVisitor(FrameRate, bool);
+
+        impl Visitor<'_> for TimecodeVisitor {
+            type Value = Tick;
+
+            fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                if self.1 {
+                    write!(f, "a drop-frame timecode like `01:00:00;00`")
+                } else {
+                    write!(f, "a timecode like `01:00:00:00`")
+                }
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Tick, E> {
+                let timecode = value.parse::<Timecode>().map_err(E::custom)?;
+                if timecode.drop_frame != self.1 {
+                    return Err(E::invalid_value(
+                        de::Unexpected::Str(value),
+                        &self,
+                    ));
+                }
+
+                timecode.to_tick(self.0).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_str(TimecodeVisitor(frame_rate, drop_frame))
+    }
+
+    frame_rate_modules! {
+        film => FILM, false;
+        fps_30 => FPS_30, false;
+        fps_60 => FPS_60, false;
+        ntsc => NTSC, false;
+        ntsc_drop_frame => NTSC, true;
+        ntsc_film => NTSC_FILM, false;
+        ntsc_high => NTSC_HIGH, false;
+        ntsc_high_drop_frame => NTSC_HIGH, true;
+        pal => PAL, false;
+        pal_high => PAL_HIGH, false;
+    }
+}
+
+/// Write a [`Tick`] as a frame number, e.g. `48`.
+///
+/// Pick the submodule for the frame rate, e.g.
+/// `#[serde(with = "frame_tick::with::frames::film")]`. Serializing fails for
+/// ticks that are not the first tick of a frame.
+pub mod frames {
+    use super::*;
+
+    fn serialize<S: Serializer>(
+        tick: &Tick,
+        frame_rate: FrameRate,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let frame = div_round(
+            tick.0 as i128 * frame_rate.num() as i128,
+            TICKS_PER_SECOND as i128 * frame_rate.den() as i128,
+            Rounding::Floor,
+        );
+        if tick.0 as i128 != frame_start(frame, frame_rate) {
+            return Err(ser::Error::custom(
+                "tick is not the first tick of a frame",
+            ));
+        }
+
+        serializer.serialize_i64(frame as _)
+    }
+
+    fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+        frame_rate: FrameRate,
+    ) -> Result<Tick, D::Error> {
+        let frame = i64::deserialize(deserializer)?;
+
+        i64::try_from(frame_start(frame as _, frame_rate))
+            .map(Tick)
+            .map_err(|_| {
+                de::Error::invalid_value(
+                    de::Unexpected::Signed(frame),
+                    &"a frame number in range of a tick",
+                )
+            })
+    }
+
+    frame_rate_modules! {
+        film => FILM;
+        fps_30 => FPS_30;
+        fps_60 => FPS_60;
+        ntsc => NTSC;
+        ntsc_film => NTSC_FILM;
+        ntsc_high => NTSC_HIGH;
+        pal => PAL;
+        pal_high => PAL_HIGH;
+    }
+}
+
+/// Write a [`FrameRate`] as a string, e.g. `"24"` or
+/// `"30000/1001"`.
+///
+/// Deserializing accepts anything [`FrameRate::from_str()`] does, e.g.
+/// `"29.97"`.
+///
+/// [`FrameRate::from_str()`]: core::str::FromStr::from_str
+pub mod frame_rate {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(
+        frame_rate: &FrameRate,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        if 1 == frame_rate.den() {
+            serializer.collect_str(&frame_rate.num())
+        } else {
+            serializer.collect_str(&format_args!(
+                "{}/{}",
+                frame_rate.num(),
+                frame_rate.den()
+            ))
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<FrameRate, D::Error> {
+        deserializer.deserialize_str(StrVisitor(
+            "a frame rate like `24`, `29.97` or `30000/1001`",
+            parse_frame_rate,
+        ))
+    }
+
+    fn parse_frame_rate(s: &str) -> Result<FrameRate, &'static str> {
+        s.parse().map_err(|error| match error {
+            FrameRateError::Zero => "a nonzero frame rate",
+            FrameRateError::Overflow => "a frame rate in range",
+            FrameRateError::Syntax | FrameRateError::Inexact => {
+                "a frame rate like `24`, `29.97` or `30000/1001`"
+            }
+        })
+    }
+}
+
+/// Deserializes a string with a parse function that reports what it
+/// expected instead.
+struct StrVisitor<T>(&'static str, fn(&str) -> Result<T, &'static str>);
+
+impl<T> Visitor<'_> for StrVisitor<T> {
+    type Value = T;
+
+    fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.0)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<T, E> {
+        (self.1)(value).map_err(|reason| {
+            E::invalid_value(de::Unexpected::Str(value), &reason)
+        })
+    }
+}